@@ -5,6 +5,7 @@ use oramacore_client::collection::{CollectionManager, CollectionManagerConfig, N
 use oramacore_client::error::Result;
 use oramacore_client::stream_manager::{AnswerConfig, CreateAiSessionConfig};
 use oramacore_client::types::{LlmConfig, LlmProvider, Message, Role};
+use oramacore_client::utils::truncate_chars;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,14 +23,12 @@ async fn main() -> Result<()> {
 
     // Example 1: NLP Search
     println!("=== NLP Search ===");
-    let nlp_params = NlpSearchParams {
-        query: "What are the benefits of machine learning?".to_string(),
-        llm_config: Some(LlmConfig {
+    let nlp_params = NlpSearchParams::new("What are the benefits of machine learning?")
+        .with_llm_config(LlmConfig {
             provider: LlmProvider::OpenAI,
             model: "gpt-4".to_string(),
-        }),
-        user_id: Some("user-123".to_string()),
-    };
+        })
+        .with_user_id("user-123");
 
     let nlp_results = client.ai.nlp_search::<Document>(nlp_params).await?;
     println!("NLP search found {} results", nlp_results.len());
@@ -140,12 +139,13 @@ async fn main() -> Result<()> {
             Role::User => "User",
             Role::Assistant => "Assistant",
         };
+        let preview = truncate_chars(&message.content, 100);
         println!(
             "{}. {}: {}",
             i + 1,
             role,
-            if message.content.len() > 100 {
-                format!("{}...", &message.content[..100])
+            if preview.len() < message.content.len() {
+                format!("{preview}...")
             } else {
                 message.content.clone()
             }
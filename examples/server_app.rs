@@ -227,32 +227,21 @@ async fn main() -> Result<()> {
 
     // Performance testing
     println!("\nPerformance testing - Multiple concurrent searches...");
-    let mut tasks = Vec::new();
-
-    for i in 0..5 {
-        let client_clone = collection_client.clone();
-        let query = format!("search query {i}");
-
-        let task = tokio::spawn(async move {
-            let params = oramacore_client::types::SearchParams::new(&query).with_limit(3);
-            client_clone.search::<Product>(&params).await
-        });
-
-        tasks.push(task);
-    }
+    let queries: Vec<_> = (0..5)
+        .map(|i| {
+            oramacore_client::types::SearchParams::new(format!("search query {i}")).with_limit(3)
+        })
+        .collect();
 
     // Wait for all searches to complete
-    let mut successful_searches = 0;
-    for task in tasks {
-        match task.await {
-            Ok(Ok(result)) => {
-                successful_searches += 1;
-                println!("   ✅ Search completed: {} results", result.hits.len());
-            }
-            Ok(Err(e)) => println!("   ❌ Search failed: {e}"),
-            Err(e) => println!("   ❌ Task failed: {e}"),
-        }
+    let results = collection_client.search_many::<Product>(&queries).await;
+    for result in &results.ok {
+        println!("   ✅ Search completed: {} results", result.hits.len());
+    }
+    for e in &results.errors {
+        println!("   ❌ Search failed: {e}");
     }
+    let successful_searches = results.ok.len();
 
     println!("📊 Performance test completed: {successful_searches}/5 searches successful");
 
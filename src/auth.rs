@@ -1,14 +1,18 @@
 //! Authentication handling for Orama client.
 
+use std::fmt;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
 
 use crate::error::{OramaError, Result};
+use crate::utils::redact;
 
 /// JWT response from authentication endpoint
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 struct JwtRequestResponse {
     jwt: String,
     #[serde(rename = "writerURL")]
@@ -21,8 +25,37 @@ struct JwtRequestResponse {
     expires_in: u64,
 }
 
-/// Authentication configuration for API key authentication
+/// Safety margin subtracted from the JWT lifetime before it is considered expired
+const JWT_EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// JWT response cached alongside its expiry instant
 #[derive(Debug, Clone)]
+struct CachedJwt {
+    response: JwtRequestResponse,
+    expires_at: Instant,
+}
+
+impl CachedJwt {
+    fn new(response: JwtRequestResponse) -> Self {
+        let lifetime = Duration::from_secs(response.expires_in).saturating_sub(JWT_EXPIRY_MARGIN);
+        Self {
+            response,
+            expires_at: Instant::now() + lifetime,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        Instant::now() < self.expires_at
+    }
+}
+
+/// Treat an empty URL the same as an unset one
+fn non_empty(url: &Option<String>) -> Option<&str> {
+    url.as_deref().filter(|u| !u.trim().is_empty())
+}
+
+/// Authentication configuration for API key authentication
+#[derive(Clone)]
 pub struct ApiKeyAuth {
     pub api_key: String,
     pub reader_url: Option<String>,
@@ -30,7 +63,7 @@ pub struct ApiKeyAuth {
 }
 
 /// Authentication configuration for JWT authentication
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct JwtAuth {
     pub auth_jwt_url: String,
     pub collection_id: String,
@@ -47,14 +80,57 @@ pub enum AuthConfig {
 }
 
 /// Authentication reference containing bearer token and base URL
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct AuthRef {
     pub bearer: String,
     pub base_url: String,
 }
 
+impl fmt::Debug for JwtRequestResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JwtRequestResponse")
+            .field("jwt", &redact(&self.jwt))
+            .field("writer_url", &self.writer_url)
+            .field("reader_api_key", &redact(&self.reader_api_key))
+            .field("reader_url", &self.reader_url)
+            .field("expires_in", &self.expires_in)
+            .finish()
+    }
+}
+
+impl fmt::Debug for ApiKeyAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeyAuth")
+            .field("api_key", &redact(&self.api_key))
+            .field("reader_url", &self.reader_url)
+            .field("writer_url", &self.writer_url)
+            .finish()
+    }
+}
+
+impl fmt::Debug for JwtAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JwtAuth")
+            .field("auth_jwt_url", &self.auth_jwt_url)
+            .field("collection_id", &self.collection_id)
+            .field("private_api_key", &redact(&self.private_api_key))
+            .field("reader_url", &self.reader_url)
+            .field("writer_url", &self.writer_url)
+            .finish()
+    }
+}
+
+impl fmt::Debug for AuthRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthRef")
+            .field("bearer", &redact(&self.bearer))
+            .field("base_url", &self.base_url)
+            .finish()
+    }
+}
+
 /// Target for the request (reader or writer)
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Target {
     Reader,
     Writer,
@@ -65,12 +141,30 @@ pub enum Target {
 pub struct Auth {
     config: AuthConfig,
     client: Arc<Client>,
+    reader_jwt: Arc<RwLock<Option<CachedJwt>>>,
+    writer_jwt: Arc<RwLock<Option<CachedJwt>>>,
 }
 
 impl Auth {
     /// Create a new authentication handler
     pub fn new(config: AuthConfig, client: Arc<Client>) -> Self {
-        Self { config, client }
+        Self {
+            config,
+            client,
+            reader_jwt: Arc::new(RwLock::new(None)),
+            writer_jwt: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Whether this handler authenticates through the JWT flow
+    pub fn is_jwt(&self) -> bool {
+        matches!(self.config, AuthConfig::Jwt(_))
+    }
+
+    /// Discard any cached JWT so the next request fetches a fresh one
+    pub async fn invalidate(&self) {
+        *self.reader_jwt.write().await = None;
+        *self.writer_jwt.write().await = None;
     }
 
     /// Get authentication reference for the specified target
@@ -79,49 +173,40 @@ impl Auth {
             AuthConfig::ApiKey(config) => {
                 let bearer = config.api_key.clone();
                 let base_url = match target {
-                    Target::Writer => {
-                        config.writer_url.as_ref()
-                            .ok_or_else(|| OramaError::config(
-                                "Cannot perform a request to a writer without the writerURL. Use cluster.writerURL to configure it"
-                            ))?
-                            .clone()
-                    }
-                    Target::Reader => {
-                        config.reader_url.as_ref()
-                            .ok_or_else(|| OramaError::config(
-                                "Cannot perform a request to a reader without the readerURL. Use cluster.readerURL to configure it"
-                            ))?
-                            .clone()
-                    }
+                    Target::Writer => non_empty(&config.writer_url)
+                        .ok_or_else(|| {
+                            OramaError::config(
+                                "Cannot perform a request to a writer without a writer URL. \
+                                 Set it with ClusterConfig::with_writer_url (or Endpoints::with_writer_url)",
+                            )
+                        })?
+                        .to_string(),
+                    Target::Reader => non_empty(&config.reader_url)
+                        .ok_or_else(|| {
+                            OramaError::config(
+                                "Cannot perform a request to a reader without a reader URL. \
+                                 Set it with ClusterConfig::with_read_url (or Endpoints::with_reader_url)",
+                            )
+                        })?
+                        .to_string(),
                 };
 
                 Ok(AuthRef { bearer, base_url })
             }
             AuthConfig::Jwt(config) => {
-                let jwt_response = self
-                    .get_jwt_token(
-                        &config.auth_jwt_url,
-                        &config.collection_id,
-                        &config.private_api_key,
-                        "write",
-                    )
-                    .await?;
+                let jwt_response = self.get_cached_jwt(config, target).await?;
 
                 let (bearer, base_url) = match target {
                     Target::Reader => {
-                        let base_url = config
-                            .reader_url
-                            .as_ref()
+                        let base_url = non_empty(&config.reader_url)
                             .unwrap_or(&jwt_response.reader_url)
-                            .clone();
+                            .to_string();
                         (jwt_response.reader_api_key, base_url)
                     }
                     Target::Writer => {
-                        let base_url = config
-                            .writer_url
-                            .as_ref()
+                        let base_url = non_empty(&config.writer_url)
                             .unwrap_or(&jwt_response.writer_url)
-                            .clone();
+                            .to_string();
                         (jwt_response.jwt, base_url)
                     }
                 };
@@ -131,6 +216,36 @@ impl Auth {
         }
     }
 
+    /// Get a JWT scoped to the target from the cache, fetching a new one if
+    /// missing or expired
+    async fn get_cached_jwt(&self, config: &JwtAuth, target: Target) -> Result<JwtRequestResponse> {
+        let (slot, scope) = match target {
+            Target::Reader => (&self.reader_jwt, "read"),
+            Target::Writer => (&self.writer_jwt, "write"),
+        };
+
+        {
+            let cache = slot.read().await;
+            if let Some(cached) = cache.as_ref().filter(|c| c.is_valid()) {
+                return Ok(cached.response.clone());
+            }
+        }
+
+        let jwt_response = self
+            .get_jwt_token(
+                &config.auth_jwt_url,
+                &config.collection_id,
+                &config.private_api_key,
+                scope,
+            )
+            .await?;
+
+        let mut cache = slot.write().await;
+        *cache = Some(CachedJwt::new(jwt_response.clone()));
+
+        Ok(jwt_response)
+    }
+
     /// Get JWT token from authentication endpoint
     async fn get_jwt_token(
         &self,
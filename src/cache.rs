@@ -0,0 +1,159 @@
+//! In-memory caching of search results.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::types::SearchParams;
+
+/// Configuration for the search result cache
+#[derive(Debug, Clone)]
+pub struct SearchCacheConfig {
+    /// Maximum number of cached results
+    pub capacity: usize,
+    /// How long a cached result stays valid
+    pub ttl: Duration,
+}
+
+impl SearchCacheConfig {
+    /// Create a new SearchCacheConfig
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self { capacity, ttl }
+    }
+}
+
+impl Default for SearchCacheConfig {
+    fn default() -> Self {
+        Self::new(1000, Duration::from_secs(60))
+    }
+}
+
+/// Cached search response, stored as JSON so it can serve any document type
+#[derive(Debug)]
+struct CacheEntry {
+    value: serde_json::Value,
+    inserted_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    map: HashMap<u64, CacheEntry>,
+    // Least recently used keys first
+    order: VecDeque<u64>,
+}
+
+impl CacheEntries {
+    fn touch(&mut self, key: u64) {
+        if let Some(position) = self.order.iter().position(|k| *k == key) {
+            self.order.remove(position);
+        }
+        self.order.push_back(key);
+    }
+
+    fn remove(&mut self, key: u64) {
+        self.map.remove(&key);
+        if let Some(position) = self.order.iter().position(|k| *k == key) {
+            self.order.remove(position);
+        }
+    }
+}
+
+/// LRU cache of search results with a TTL.
+///
+/// Every write to the collection bumps a generation counter that is part of
+/// the cache key, so results cached before a write are never served after it.
+#[derive(Debug)]
+pub struct SearchCache {
+    config: SearchCacheConfig,
+    generation: AtomicU64,
+    entries: Mutex<CacheEntries>,
+}
+
+impl SearchCache {
+    /// Create a new, empty cache
+    pub fn new(config: SearchCacheConfig) -> Self {
+        Self {
+            config,
+            generation: AtomicU64::new(0),
+            entries: Mutex::new(CacheEntries::default()),
+        }
+    }
+
+    /// Invalidate all cached results
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        let mut entries = self.entries.lock().unwrap();
+        entries.map.clear();
+        entries.order.clear();
+    }
+
+    /// Number of cached results, including expired ones not yet evicted
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().map.len()
+    }
+
+    /// Whether the cache holds no results
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Compute the cache key for a search on a collection
+    pub(crate) fn key(&self, collection_id: &str, params: &SearchParams) -> Result<u64> {
+        // Going through `Value` sorts object keys, so equal params hash equally
+        let params = serde_json::to_value(params)?.to_string();
+
+        let mut hasher = DefaultHasher::new();
+        collection_id.hash(&mut hasher);
+        self.generation.load(Ordering::SeqCst).hash(&mut hasher);
+        params.hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+
+    /// Get a cached result if present and not expired
+    pub(crate) fn get(&self, key: u64) -> Option<serde_json::Value> {
+        let mut entries = self.entries.lock().unwrap();
+
+        let expired = match entries.map.get(&key) {
+            Some(entry) => entry.inserted_at.elapsed() >= self.config.ttl,
+            None => return None,
+        };
+
+        if expired {
+            entries.remove(key);
+            return None;
+        }
+
+        entries.touch(key);
+        entries.map.get(&key).map(|entry| entry.value.clone())
+    }
+
+    /// Store a result, evicting the least recently used entries when full
+    pub(crate) fn insert(&self, key: u64, value: serde_json::Value) {
+        if self.config.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.map.insert(
+            key,
+            CacheEntry {
+                value,
+                inserted_at: Instant::now(),
+            },
+        );
+        entries.touch(key);
+
+        while entries.map.len() > self.config.capacity {
+            match entries.order.pop_front() {
+                Some(oldest) => {
+                    entries.map.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+}
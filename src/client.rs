@@ -1,15 +1,25 @@
 //! HTTP client for Orama API operations.
 
 use std::collections::HashMap;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
-use reqwest::{Client as ReqwestClient, Method, Response};
+use bytes::Bytes;
+use futures::stream::{Stream, StreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::{
+    Certificate, Client as ReqwestClient, ClientBuilder, Method, RequestBuilder, Response,
+    StatusCode,
+};
+use reqwest_eventsource::{Event, EventSource};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use url::Url;
 
-use crate::auth::{Auth, Target};
-use crate::error::{OramaError, Result};
+use crate::auth::{Auth, AuthRef, Target};
+use crate::error::{OramaError, RequestContext, Result};
 
 /// API key position in the request
 #[derive(Debug, Clone, PartialEq)]
@@ -27,6 +37,12 @@ pub struct ClientRequest<T> {
     pub api_key_position: ApiKeyPosition,
     pub body: Option<T>,
     pub params: Option<HashMap<String, String>>,
+    /// Extra headers sent with the request
+    pub headers: Option<HashMap<String, String>>,
+    /// Timeout for the whole request, overriding the client default
+    pub timeout: Option<Duration>,
+    /// Override the client's JSON repair setting for this request
+    pub repair_response: Option<bool>,
 }
 
 impl<T> ClientRequest<T> {
@@ -39,6 +55,9 @@ impl<T> ClientRequest<T> {
             api_key_position,
             body: None,
             params: None,
+            headers: None,
+            timeout: None,
+            repair_response: None,
         }
     }
 
@@ -51,6 +70,9 @@ impl<T> ClientRequest<T> {
             api_key_position,
             body: Some(body),
             params: None,
+            headers: None,
+            timeout: None,
+            repair_response: None,
         }
     }
 
@@ -60,6 +82,12 @@ impl<T> ClientRequest<T> {
         self
     }
 
+    /// Enable or disable repairing malformed JSON in the response
+    pub fn with_json_repair(mut self, repair: bool) -> Self {
+        self.repair_response = Some(repair);
+        self
+    }
+
     /// Add a single query parameter
     pub fn with_param<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
         let mut params = self.params.unwrap_or_default();
@@ -67,64 +95,618 @@ impl<T> ClientRequest<T> {
         self.params = Some(params);
         self
     }
+
+    /// Set a timeout for the whole request
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Add a single header
+    pub fn with_header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        let mut headers = self.headers.unwrap_or_default();
+        headers.insert(name.into(), value.into());
+        self.headers = Some(headers);
+        self
+    }
+}
+
+impl<T: Serialize> ClientRequest<T> {
+    /// The JSON body that will be sent, with secrets such as API keys redacted
+    pub fn serialized_body(&self) -> Result<Option<String>> {
+        let Some(body) = &self.body else {
+            return Ok(None);
+        };
+
+        let mut value = serde_json::to_value(body)?;
+        redact_secrets(&mut value);
+        Ok(Some(value.to_string()))
+    }
+}
+
+/// Redact values of keys that look like credentials, recursively
+fn redact_secrets(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    serde_json::Value::String(secret) if is_secret_key(key) => {
+                        *secret = crate::utils::redact(secret);
+                    }
+                    _ => redact_secrets(value),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// Whether `key` names a credential, such as `api_key`, `writeAPIKey` or
+/// `access_token`. Only whole words count, so `max_tokens` is left alone.
+fn is_secret_key(key: &str) -> bool {
+    const SECRET_SUFFIXES: [&str; 5] = ["apikey", "token", "jwt", "password", "secret"];
+
+    let key: String = key
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect();
+    SECRET_SUFFIXES.iter().any(|suffix| key.ends_with(suffix))
+}
+
+/// Chunks of a streamed response body, as returned by
+/// [`OramaClient::stream_response`]
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>;
+
+/// Events of a server-sent events stream
+pub(crate) type EventStream =
+    Pin<Box<dyn Stream<Item = std::result::Result<Event, reqwest_eventsource::Error>> + Send>>;
+
+/// Metadata extracted from response headers
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResponseMeta {
+    pub request_id: Option<String>,
+    pub rate_limit_remaining: Option<u32>,
+    pub rate_limit_reset: Option<u64>,
+}
+
+impl ResponseMeta {
+    /// Extract metadata from response headers
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+        Self {
+            request_id: header("x-request-id").map(String::from),
+            rate_limit_remaining: header("x-ratelimit-remaining").and_then(|v| v.parse().ok()),
+            rate_limit_reset: header("x-ratelimit-reset").and_then(|v| v.parse().ok()),
+        }
+    }
+}
+
+/// Join a request path onto a base URL, preserving any path prefix on the base.
+///
+/// `https://x.com/api` + `/v1/collections` yields `https://x.com/api/v1/collections`
+/// regardless of trailing or leading slashes.
+pub(crate) fn join_url(base_url: &str, path: &str) -> Result<Url> {
+    let mut base = Url::parse(base_url)?;
+
+    if !base.path().ends_with('/') {
+        let with_slash = format!("{}/", base.path());
+        base.set_path(&with_slash);
+    }
+
+    Ok(base.join(path.trim_start_matches('/'))?)
+}
+
+/// User agent sent with every request
+pub const USER_AGENT: &str = concat!("oramacore-client-rust/", env!("CARGO_PKG_VERSION"));
+
+/// Default maximum response body size (64 MiB)
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Options controlling the behavior of the HTTP client
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    /// Maximum number of bytes read from a response body
+    pub max_response_bytes: usize,
+    /// Repair malformed JSON responses instead of failing (off by default)
+    pub repair_responses: bool,
+    /// Appended to the default user agent to identify the calling application
+    pub user_agent_suffix: Option<String>,
+    /// Maximum idle connections kept per host
+    pub pool_max_idle_per_host: Option<usize>,
+    /// How long idle connections are kept in the pool
+    pub pool_idle_timeout: Option<Duration>,
+    /// Use HTTP/2 without negotiation, for backends known to support it
+    pub http2_prior_knowledge: bool,
+    /// Log the (redacted) request body at debug level when a request fails
+    pub debug_body: bool,
+    /// Maximum number of requests in flight at once across all operations
+    /// sharing this client; further requests wait for a free slot
+    pub max_concurrency: Option<usize>,
+    /// Extra trusted root certificates, e.g. an internal CA
+    pub root_certificates: Vec<Certificate>,
+    /// Skip TLS certificate verification; see [`danger_accept_invalid_certs`](Self::danger_accept_invalid_certs)
+    pub accept_invalid_certs: bool,
+    /// Default timeout for each request
+    pub timeout: Option<Duration>,
+    /// Connection settings used for reader requests instead of these
+    pub reader_options: Option<Box<ClientOptions>>,
+    /// Connection settings used for writer requests instead of these
+    pub writer_options: Option<Box<ClientOptions>>,
+}
+
+impl ClientOptions {
+    /// Create the default client options
+    pub fn new() -> Self {
+        Self {
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            repair_responses: false,
+            user_agent_suffix: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            http2_prior_knowledge: false,
+            debug_body: false,
+            max_concurrency: None,
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            timeout: None,
+            reader_options: None,
+            writer_options: None,
+        }
+    }
+
+    /// Set the maximum response body size in bytes
+    pub fn with_max_response_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_response_bytes = max_bytes;
+        self
+    }
+
+    /// Enable or disable repairing malformed JSON responses by default
+    pub fn with_repair_responses(mut self, repair: bool) -> Self {
+        self.repair_responses = repair;
+        self
+    }
+
+    /// Append a suffix such as `myapp/2.0` to the user agent
+    pub fn with_user_agent_suffix<S: Into<String>>(mut self, suffix: S) -> Self {
+        self.user_agent_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Set the maximum idle connections kept per host
+    pub fn with_pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max_idle);
+        self
+    }
+
+    /// Set how long idle connections are kept in the pool
+    pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Force HTTP/2 without protocol negotiation
+    pub fn with_http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http2_prior_knowledge = enabled;
+        self
+    }
+
+    /// Log the redacted request body when a request fails
+    pub fn with_debug_body(mut self, enabled: bool) -> Self {
+        self.debug_body = enabled;
+        self
+    }
+
+    /// Limit the number of requests in flight at once
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+
+    /// Trust an additional root certificate, e.g. the CA of a self-hosted
+    /// deployment. Parse one with `Certificate::from_pem`.
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Accept any TLS certificate, including self-signed and expired ones.
+    ///
+    /// **Dangerous:** this disables server authentication, so anyone able to
+    /// intercept traffic can impersonate the server and read the API keys
+    /// sent with each request. Only use it against local development setups;
+    /// prefer [`add_root_certificate`](Self::add_root_certificate) otherwise.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Set the default timeout for each request
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Use different connection settings for one target, e.g. a longer
+    /// timeout for bulk writes than for searches.
+    ///
+    /// Only connection-level settings (timeout, pool, HTTP/2, TLS and user
+    /// agent) are taken from `options`; the rest always come from `self`.
+    pub fn with_target_options(mut self, target: Target, options: ClientOptions) -> Self {
+        let options = Some(Box::new(options));
+        match target {
+            Target::Reader => self.reader_options = options,
+            Target::Writer => self.writer_options = options,
+        }
+        self
+    }
+
+    /// The connection settings that apply to a target
+    pub fn for_target(&self, target: Target) -> &ClientOptions {
+        let options = match target {
+            Target::Reader => &self.reader_options,
+            Target::Writer => &self.writer_options,
+        };
+        options.as_deref().unwrap_or(self)
+    }
+
+    /// Build a reqwest client from the connection-level settings
+    fn build_http_client(&self) -> Result<ReqwestClient> {
+        let mut builder = self.apply_tls(ReqwestClient::builder().user_agent(self.user_agent()));
+
+        if let Some(max_idle) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(idle_timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(idle_timeout);
+        }
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        Ok(builder.build()?)
+    }
+
+    /// Apply the TLS settings to a reqwest client builder
+    pub(crate) fn apply_tls(&self, mut builder: ClientBuilder) -> ClientBuilder {
+        for certificate in &self.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        if self.accept_invalid_certs {
+            warn!("TLS certificate verification is disabled");
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        builder
+    }
+
+    /// The full user agent string sent with requests
+    pub fn user_agent(&self) -> String {
+        match &self.user_agent_suffix {
+            Some(suffix) => format!("{USER_AGENT} {suffix}"),
+            None => USER_AGENT.to_string(),
+        }
+    }
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// HTTP client for Orama API
 #[derive(Debug, Clone)]
 pub struct OramaClient {
-    client: Arc<ReqwestClient>,
+    reader_client: Arc<ReqwestClient>,
+    writer_client: Arc<ReqwestClient>,
     auth: Auth,
+    options: ClientOptions,
+    // Shared by clones, so the limit applies to every namespace
+    concurrency: Option<Arc<Semaphore>>,
 }
 
 impl OramaClient {
     /// Create a new Orama client
     pub fn new(auth: Auth) -> Result<Self> {
-        let client = ReqwestClient::builder()
-            .user_agent("oramacore-client-rust/1.2.0")
-            .build()?;
+        Self::with_options(auth, ClientOptions::default())
+    }
+
+    /// Create a new Orama client with custom options
+    pub fn with_options(auth: Auth, options: ClientOptions) -> Result<Self> {
+        let reader_client = Arc::new(options.for_target(Target::Reader).build_http_client()?);
+        // Share one connection pool unless the writer is configured separately
+        let writer_client = match &options.writer_options {
+            Some(writer_options) => Arc::new(writer_options.build_http_client()?),
+            None if options.reader_options.is_none() => reader_client.clone(),
+            None => Arc::new(options.build_http_client()?),
+        };
+
+        let concurrency = options
+            .max_concurrency
+            .map(|max| Arc::new(Semaphore::new(max.max(1))));
 
         Ok(Self {
-            client: Arc::new(client),
+            reader_client,
+            writer_client,
             auth,
+            options,
+            concurrency,
         })
     }
 
+    /// Get the client options
+    pub fn options(&self) -> &ClientOptions {
+        &self.options
+    }
+
     /// Make a request and return the deserialized response
+    ///
+    /// On a 401 from a JWT-authenticated client, the cached token is
+    /// refreshed and the request is retried once.
     pub async fn request<T, R>(&self, req: ClientRequest<T>) -> Result<R>
     where
         T: Serialize,
         R: DeserializeOwned,
     {
-        let response = self.get_response(req).await?;
+        let (result, _) = self.request_with_meta(req).await?;
+        Ok(result)
+    }
+
+    /// Make a request and return the deserialized response along with
+    /// metadata read from the response headers
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "orama.request",
+        skip_all,
+        fields(method = %req.method, path = %req.path, target = ?req.target)
+    ))]
+    pub async fn request_with_meta<T, R>(&self, req: ClientRequest<T>) -> Result<(R, ResponseMeta)>
+    where
+        T: Serialize,
+        R: DeserializeOwned,
+    {
+        // Held until the body has been read
+        let _permit = self.acquire_slot().await;
+
+        let response = self.send_with_refresh(&req).await?;
 
         if !response.status().is_success() {
             let status = response.status().as_u16();
-            let text = response.text().await.unwrap_or_default();
+            let text = self.read_body(response).await.unwrap_or_default();
+
+            let context = RequestContext::new(req.method.as_str(), &req.path);
 
-            return Err(match status {
-                401 => OramaError::auth("Unauthorized: are you using the correct API Key?"),
-                400 => OramaError::api(status, format!("Bad Request: {text}")),
-                _ => OramaError::api(status, text),
-            });
+            if self.options.debug_body {
+                if let Ok(Some(body)) = req.serialized_body() {
+                    debug!("Request failed ({}) {}: {}", status, context, body);
+                }
+            }
+
+            return Err(OramaError::from_response(status, text, Some(context)));
         }
 
-        // Use robust JSON parsing for API responses
-        let text = response.text().await?;
-        let result = crate::utils::safe_json_parse::<R>(&text)
-            .map_err(|e| OramaError::generic(format!("Failed to parse API response: {e}")))?;
-        Ok(result)
+        let meta = ResponseMeta::from_headers(response.headers());
+
+        let text = self.read_body(response).await?;
+
+        // 204 No Content and friends: accept them for unit-like response
+        // types (`()`, `Option<_>`) that deserialize from `null`
+        if text.trim().is_empty() {
+            let result = serde_json::from_str::<R>("null").map_err(|_| {
+                OramaError::generic(format!(
+                    "Empty response body from {} {}",
+                    req.method, req.path
+                ))
+            })?;
+            return Ok((result, meta));
+        }
+
+        let repair = req.repair_response.unwrap_or(self.options.repair_responses);
+
+        let result = if repair {
+            // Robust parsing for AI-generated responses
+            crate::utils::safe_json_parse::<R>(&text)
+                .map_err(|e| OramaError::deserialize::<R, _>(&text, e.to_string()))?
+        } else {
+            serde_json::from_str::<R>(&text)
+                .map_err(|e| OramaError::deserialize::<R, _>(&text, e.to_string()))?
+        };
+        Ok((result, meta))
     }
 
-    /// Make a request and return the raw response
+    /// Make a request and return the raw response, for endpoints this crate
+    /// does not model.
+    ///
+    /// The request is authenticated and routed to its target like
+    /// [`request`](Self::request), but the status is not checked: non-2xx
+    /// responses are returned as-is. The concurrency slot is released once
+    /// the response headers arrive.
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "orama.request",
+        skip_all,
+        fields(method = %req.method, path = %req.path, target = ?req.target)
+    ))]
     pub async fn get_response<T>(&self, req: ClientRequest<T>) -> Result<Response>
     where
         T: Serialize,
     {
+        let _permit = self.acquire_slot().await;
+        self.send(&req).await
+    }
+
+    /// Make a request and stream the response body, for streaming endpoints
+    /// this crate does not model.
+    ///
+    /// Authentication, target resolution and the token refresh on 401 work
+    /// as in [`request`](Self::request), and a non-2xx status is returned as
+    /// the matching [`OramaError`] before any of the body is handed back.
+    /// The concurrency slot is released once the response headers arrive.
+    ///
+    /// ```no_run
+    /// # async fn example(client: oramacore_client::client::OramaClient) -> oramacore_client::Result<()> {
+    /// use futures::StreamExt;
+    /// use oramacore_client::auth::Target;
+    /// use oramacore_client::client::{ApiKeyPosition, ClientRequest};
+    ///
+    /// let request: ClientRequest<()> = ClientRequest::get(
+    ///     "/v1/collections/my-collection/export".to_string(),
+    ///     Target::Reader,
+    ///     ApiKeyPosition::Header,
+    /// );
+    /// let mut body = client.stream_response(request).await?;
+    /// while let Some(chunk) = body.next().await {
+    ///     println!("{} bytes", chunk?.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "orama.request",
+        skip_all,
+        fields(method = %req.method, path = %req.path, target = ?req.target)
+    ))]
+    pub async fn stream_response<T>(&self, req: ClientRequest<T>) -> Result<ByteStream>
+    where
+        T: Serialize,
+    {
+        let response = {
+            let _permit = self.acquire_slot().await;
+            self.send_with_refresh(&req).await?
+        };
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let text = self.read_body(response).await.unwrap_or_default();
+            let context = RequestContext::new(req.method.as_str(), &req.path);
+            return Err(OramaError::from_response(status, text, Some(context)));
+        }
+
+        Ok(Box::pin(
+            response
+                .bytes_stream()
+                .map(|chunk| chunk.map_err(OramaError::from)),
+        ))
+    }
+
+    /// Wait for a free request slot when a concurrency limit is configured
+    async fn acquire_slot(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.concurrency {
+            // The semaphore is never closed, so acquiring cannot fail
+            Some(semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        }
+    }
+
+    /// Send a request; on a 401 from a JWT-authenticated client, refresh the
+    /// cached token and send it once more
+    async fn send_with_refresh<T>(&self, req: &ClientRequest<T>) -> Result<Response>
+    where
+        T: Serialize,
+    {
+        let response = self.send(req).await?;
+
+        if response.status() == StatusCode::UNAUTHORIZED && self.auth.is_jwt() {
+            self.auth.invalidate().await;
+            return self.send(req).await;
+        }
+
+        Ok(response)
+    }
+
+    /// Build and send a request
+    async fn send<T>(&self, req: &ClientRequest<T>) -> Result<Response>
+    where
+        T: Serialize,
+    {
+        let response = self.build(req).await?.send().await?;
+        Ok(response)
+    }
+
+    /// Open a server-sent events stream for a request, with the same
+    /// authentication as [`request`](Self::request).
+    ///
+    /// The first event is awaited so that a 401 from a JWT-authenticated
+    /// client refreshes the cached token and reconnects once.
+    pub(crate) async fn event_stream<T>(&self, req: &ClientRequest<T>) -> Result<EventStream>
+    where
+        T: Serialize,
+    {
+        let _permit = self.acquire_slot().await;
+
+        let mut event_source = self.event_source(req).await?;
+        let mut first = event_source.next().await;
+
+        let unauthorized = matches!(
+            &first,
+            Some(Err(reqwest_eventsource::Error::InvalidStatusCode(status)))
+                if *status == StatusCode::UNAUTHORIZED
+        );
+        if unauthorized && self.auth.is_jwt() {
+            event_source.close();
+            self.auth.invalidate().await;
+            event_source = self.event_source(req).await?;
+            first = event_source.next().await;
+        }
+
+        Ok(Box::pin(futures::stream::iter(first).chain(event_source)))
+    }
+
+    /// Open a server-sent events stream for a request
+    async fn event_source<T>(&self, req: &ClientRequest<T>) -> Result<EventSource>
+    where
+        T: Serialize,
+    {
+        let (auth_ref, url) = self.resolve(req).await?;
+        let request_builder = self
+            .build_with(req, auth_ref, url.clone())
+            .header("Accept", "text/event-stream")
+            .header("Cache-Control", "no-cache");
+
+        let mut event_source = EventSource::new(request_builder).map_err(|e| {
+            OramaError::stream(format!(
+                "EventSource creation failed for {} {url} (target: {:?}): {e}",
+                req.method, req.target
+            ))
+        })?;
+        // Reconnecting would POST the request again and start a second
+        // generation, so errors end the stream instead
+        event_source.set_retry_policy(Box::new(reqwest_eventsource::retry::Never));
+
+        Ok(event_source)
+    }
+
+    /// Resolve the credentials and full URL of a request
+    async fn resolve<T>(&self, req: &ClientRequest<T>) -> Result<(AuthRef, Url)> {
         let auth_ref = self.auth.get_ref(req.target).await?;
-        let base_url = Url::parse(&auth_ref.base_url)?;
-        let url = base_url.join(&req.path)?;
+        let url = join_url(&auth_ref.base_url, &req.path).map_err(|e| {
+            OramaError::config(format!(
+                "Invalid base URL '{}' for {} {} (target: {:?}): {e}",
+                auth_ref.base_url, req.method, req.path, req.target
+            ))
+        })?;
+
+        Ok((auth_ref, url))
+    }
 
-        let mut request_builder = self.client.request(req.method, url);
+    /// Build an authenticated request
+    async fn build<T>(&self, req: &ClientRequest<T>) -> Result<RequestBuilder>
+    where
+        T: Serialize,
+    {
+        let (auth_ref, url) = self.resolve(req).await?;
+        Ok(self.build_with(req, auth_ref, url))
+    }
+
+    /// Build a request against an already resolved URL
+    fn build_with<T>(&self, req: &ClientRequest<T>, auth_ref: AuthRef, url: Url) -> RequestBuilder
+    where
+        T: Serialize,
+    {
+        let mut request_builder = self.inner_for(req.target).request(req.method.clone(), url);
 
         // Set headers
         request_builder = request_builder.header("Content-Type", "application/json");
@@ -134,8 +716,16 @@ impl OramaClient {
                 request_builder.header("Authorization", format!("Bearer {}", auth_ref.bearer));
         }
 
+        for (name, value) in req.headers.iter().flatten() {
+            request_builder = request_builder.header(name, value);
+        }
+
+        if let Some(timeout) = req.timeout {
+            request_builder = request_builder.timeout(timeout);
+        }
+
         // Set query parameters
-        let mut query_params = req.params.unwrap_or_default();
+        let mut query_params = req.params.clone().unwrap_or_default();
         if req.api_key_position == ApiKeyPosition::QueryParams {
             query_params.insert("api-key".to_string(), auth_ref.bearer);
         }
@@ -145,17 +735,100 @@ impl OramaClient {
         }
 
         // Set body for POST requests
-        if let Some(body) = req.body {
-            request_builder = request_builder.json(&body);
+        if let Some(body) = &req.body {
+            request_builder = request_builder.json(body);
         }
 
-        let response = request_builder.send().await?;
-        Ok(response)
+        request_builder
+    }
+
+    /// Read a response body, aborting once it exceeds `max_response_bytes`
+    async fn read_body(&self, mut response: Response) -> Result<String> {
+        let limit = self.options.max_response_bytes;
+        let too_large = || {
+            OramaError::generic(format!(
+                "Response body exceeds the maximum allowed size of {limit} bytes"
+            ))
+        };
+
+        if response
+            .content_length()
+            .is_some_and(|len| len > limit as u64)
+        {
+            return Err(too_large());
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > limit {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    /// Open a connection to the target host so later requests can reuse it.
+    ///
+    /// Any response status counts as success; only connection failures error.
+    pub async fn warmup(&self, target: Target) -> Result<()> {
+        let auth_ref = self.auth.get_ref(target).await?;
+        self.inner_for(target)
+            .head(&auth_ref.base_url)
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    /// Start building a request to an arbitrary endpoint.
+    ///
+    /// **Unstable:** an escape hatch for API features not yet covered by the
+    /// typed namespaces. Its shape may change in any release.
+    ///
+    /// ```no_run
+    /// # async fn example(client: oramacore_client::client::OramaClient) -> oramacore_client::Result<()> {
+    /// use oramacore_client::auth::Target;
+    /// use reqwest::Method;
+    ///
+    /// let stats: serde_json::Value = client
+    ///     .call(Method::GET, "/v1/collections/my-collection/stats")
+    ///     .target(Target::Reader)
+    ///     .param("verbose", "true")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn call<P: Into<String>>(&self, method: Method, path: P) -> CallBuilder<'_> {
+        CallBuilder {
+            client: self,
+            request: ClientRequest {
+                target: Target::Writer,
+                method,
+                path: path.into(),
+                api_key_position: ApiKeyPosition::Header,
+                body: None,
+                params: None,
+                headers: None,
+                timeout: None,
+                repair_response: None,
+            },
+            body_error: None,
+        }
     }
 
-    /// Get the underlying reqwest client
+    /// Get the underlying reqwest client used for reader requests
     pub fn inner(&self) -> &ReqwestClient {
-        &self.client
+        &self.reader_client
+    }
+
+    /// Get the underlying reqwest client used for a target
+    pub fn inner_for(&self, target: Target) -> &ReqwestClient {
+        match target {
+            Target::Reader => &self.reader_client,
+            Target::Writer => &self.writer_client,
+        }
     }
 
     /// Get authentication reference for a target
@@ -163,3 +836,190 @@ impl OramaClient {
         self.auth.get_ref(target).await
     }
 }
+
+/// Fluent builder for requests to arbitrary endpoints, created by
+/// [`OramaClient::call`].
+///
+/// **Unstable:** may change in any release.
+#[derive(Debug)]
+pub struct CallBuilder<'a> {
+    client: &'a OramaClient,
+    request: ClientRequest<serde_json::Value>,
+    // Reported by `send` so the builder stays chainable
+    body_error: Option<serde_json::Error>,
+}
+
+impl CallBuilder<'_> {
+    /// Send the request to the reader or writer (defaults to the writer)
+    pub fn target(mut self, target: Target) -> Self {
+        self.request.target = target;
+        self
+    }
+
+    /// Set where the API key is sent (defaults to the `Authorization` header)
+    pub fn api_key_position(mut self, position: ApiKeyPosition) -> Self {
+        self.request.api_key_position = position;
+        self
+    }
+
+    /// Set the JSON body
+    pub fn body<T: Serialize>(mut self, body: &T) -> Self {
+        match serde_json::to_value(body) {
+            Ok(value) => self.request.body = Some(value),
+            Err(e) => self.body_error = Some(e),
+        }
+        self
+    }
+
+    /// Add a query parameter
+    pub fn param<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.request = self.request.with_param(key, value);
+        self
+    }
+
+    /// Add a header
+    pub fn header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.request = self.request.with_header(name, value);
+        self
+    }
+
+    /// Enable or disable repairing malformed JSON in the response
+    pub fn json_repair(mut self, repair: bool) -> Self {
+        self.request.repair_response = Some(repair);
+        self
+    }
+
+    /// The request that will be sent
+    pub fn request(&self) -> &ClientRequest<serde_json::Value> {
+        &self.request
+    }
+
+    /// Send the request and deserialize the response
+    pub async fn send<R: DeserializeOwned>(self) -> Result<R> {
+        if let Some(e) = self.body_error {
+            return Err(e.into());
+        }
+        self.client.request(self.request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SearchParams;
+
+    #[test]
+    fn join_url_keeps_the_base_path() {
+        let expected = "https://x.com/api/v1/collections/c/search";
+        for base in ["https://x.com/api", "https://x.com/api/"] {
+            for path in ["/v1/collections/c/search", "v1/collections/c/search"] {
+                assert_eq!(join_url(base, path).unwrap().as_str(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn join_url_on_a_bare_host() {
+        for base in ["https://x.com", "https://x.com/"] {
+            assert_eq!(
+                join_url(base, "/v1/collections").unwrap().as_str(),
+                "https://x.com/v1/collections"
+            );
+        }
+    }
+
+    #[test]
+    fn user_agent_includes_the_crate_version_and_suffix() {
+        let version = env!("CARGO_PKG_VERSION");
+
+        assert_eq!(
+            ClientOptions::new().user_agent(),
+            format!("oramacore-client-rust/{version}")
+        );
+        assert_eq!(
+            ClientOptions::new()
+                .with_user_agent_suffix("myapp/2.0")
+                .user_agent(),
+            format!("oramacore-client-rust/{version} myapp/2.0")
+        );
+    }
+
+    #[test]
+    fn serialized_search_body_has_the_expected_keys() {
+        let params = SearchParams::new("shoes")
+            .with_limit(5)
+            .with_where(serde_json::json!({ "in_stock": true }));
+        let request = ClientRequest::post(
+            "/v1/collections/c/search".to_string(),
+            Target::Reader,
+            ApiKeyPosition::QueryParams,
+            params,
+        );
+
+        let body: serde_json::Value =
+            serde_json::from_str(&request.serialized_body().unwrap().unwrap()).unwrap();
+
+        let mut keys: Vec<&str> = body
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["limit", "term", "where"]);
+        assert_eq!(body["where"]["in_stock"], true);
+    }
+
+    #[test]
+    fn serialized_body_redacts_secrets() {
+        let request = ClientRequest::post(
+            "/v1/collections/create".to_string(),
+            Target::Writer,
+            ApiKeyPosition::Header,
+            serde_json::json!({ "id": "c", "write_api_key": "secret-key", "nested": { "token": "t" } }),
+        );
+
+        let body = request.serialized_body().unwrap().unwrap();
+
+        assert!(!body.contains("secret-key"));
+        assert!(!body.contains("\"t\""));
+        assert!(body.contains("\"id\":\"c\""));
+    }
+
+    #[test]
+    fn secret_keys_match_whole_words() {
+        for key in [
+            "api_key",
+            "apiKey",
+            "writeAPIKey",
+            "token",
+            "access_token",
+            "refreshToken",
+            "jwt",
+            "password",
+            "client_secret",
+        ] {
+            assert!(is_secret_key(key), "{key} should be redacted");
+        }
+        for key in [
+            "max_tokens",
+            "tokens_used",
+            "token_count",
+            "secretary",
+            "id",
+        ] {
+            assert!(!is_secret_key(key), "{key} should be kept");
+        }
+    }
+
+    #[test]
+    fn get_requests_have_no_body() {
+        let request = ClientRequest::<()>::get(
+            "/v1/collections".to_string(),
+            Target::Reader,
+            ApiKeyPosition::QueryParams,
+        );
+
+        assert_eq!(request.serialized_body().unwrap(), None);
+    }
+}
@@ -1,18 +1,41 @@
 //! Orama Cloud client functionality.
 
+use std::collections::HashMap;
+use std::fmt;
+
+use futures::stream::{Stream, StreamExt};
 use serde::Serialize;
 
-use crate::collection::{ClusterConfig, CollectionManager, CollectionManagerConfig};
+use crate::client::ClientOptions;
+use crate::collection::{
+    ClusterConfig, CollectionManager, CollectionManagerConfig, Endpoints, WriteReport,
+};
 use crate::error::Result;
 use crate::types::*;
+use crate::utils::redact;
 
 /// Configuration for OramaCloud
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ProjectManagerConfig {
     pub project_id: String,
     pub api_key: String,
     pub cluster: Option<ClusterConfig>,
     pub auth_jwt_url: Option<String>,
+    pub endpoints: Option<Endpoints>,
+    pub client_options: Option<ClientOptions>,
+}
+
+impl fmt::Debug for ProjectManagerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProjectManagerConfig")
+            .field("project_id", &self.project_id)
+            .field("api_key", &redact(&self.api_key))
+            .field("cluster", &self.cluster)
+            .field("auth_jwt_url", &self.auth_jwt_url)
+            .field("endpoints", &self.endpoints)
+            .field("client_options", &self.client_options)
+            .finish()
+    }
 }
 
 /// Cloud search parameters (uses datasources instead of indexes)
@@ -40,6 +63,9 @@ pub struct CloudSearchParams {
     pub tolerance: Option<u32>,
     #[serde(rename = "userID", skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
+    /// Maximum number of hits kept per datasource (applied client-side)
+    #[serde(skip)]
+    pub limit_per_datasource: Option<usize>,
 }
 
 /// Data source operations namespace
@@ -59,7 +85,7 @@ impl DataSourceNamespace {
     }
 
     /// Insert documents into the data source
-    pub async fn insert_documents<T>(&self, documents: Vec<T>) -> Result<()>
+    pub async fn insert_documents<T>(&self, documents: Vec<T>) -> Result<WriteReport>
     where
         T: serde::Serialize,
     {
@@ -72,7 +98,7 @@ impl DataSourceNamespace {
     }
 
     /// Upsert documents in the data source
-    pub async fn upsert_documents<T>(&self, documents: Vec<T>) -> Result<()>
+    pub async fn upsert_documents<T>(&self, documents: Vec<T>) -> Result<WriteReport>
     where
         T: serde::Serialize,
     {
@@ -97,6 +123,12 @@ impl OramaCloud {
         if let Some(auth_jwt_url) = config.auth_jwt_url {
             collection_config = collection_config.with_auth_jwt_url(auth_jwt_url);
         }
+        if let Some(endpoints) = config.endpoints {
+            collection_config = collection_config.with_endpoints(endpoints);
+        }
+        if let Some(client_options) = config.client_options {
+            collection_config = collection_config.with_client_options(client_options);
+        }
 
         let client = CollectionManager::new(collection_config).await?;
 
@@ -108,24 +140,32 @@ impl OramaCloud {
     where
         T: for<'de> serde::Deserialize<'de>,
     {
-        // Convert CloudSearchParams to SearchParams
-        let search_params = SearchParams {
-            term: params.term.clone(),
-            mode: params.mode.clone(),
-            limit: params.limit,
-            offset: params.offset,
-            properties: params.properties.clone(),
-            where_clause: params.where_clause.clone(),
-            facets: params.facets.clone(),
-            indexes: Some(params.datasources.clone()), // Map datasources to indexes
-            datasource_ids: None,
-            exact: params.exact,
-            threshold: params.threshold,
-            tolerance: params.tolerance,
-            user_id: params.user_id.clone(),
-        };
+        let mut result: SearchResult<T> = self.client.search(&params.to_search_params()).await?;
+        params.apply_limit_per_datasource(&mut result);
+        Ok(result)
+    }
 
-        self.client.search(&search_params).await
+    /// Stream every page of a search, following `next_cursor` until the last page
+    pub fn search_paginated<'a, T>(
+        &'a self,
+        params: &'a CloudSearchParams,
+    ) -> impl Stream<Item = Result<SearchResult<T>>> + 'a
+    where
+        T: for<'de> serde::Deserialize<'de> + 'a,
+    {
+        self.client
+            .search_pages::<T>(&params.to_search_params())
+            .map(move |page| {
+                page.map(|mut page| {
+                    params.apply_limit_per_datasource(&mut page);
+                    page
+                })
+            })
+    }
+
+    /// Count the documents matching a search across its datasources
+    pub async fn count(&self, params: &CloudSearchParams) -> Result<u32> {
+        self.client.count(&params.to_search_params()).await
     }
 
     /// Get a data source namespace for operations
@@ -174,6 +214,8 @@ impl ProjectManagerConfig {
             api_key: api_key.into(),
             cluster: None,
             auth_jwt_url: None,
+            endpoints: None,
+            client_options: None,
         }
     }
 
@@ -188,9 +230,64 @@ impl ProjectManagerConfig {
         self.auth_jwt_url = Some(url.into());
         self
     }
+
+    /// Override the default endpoints
+    pub fn with_endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = Some(endpoints);
+        self
+    }
+
+    /// Set HTTP client options
+    pub fn with_client_options(mut self, options: ClientOptions) -> Self {
+        self.client_options = Some(options);
+        self
+    }
 }
 
 impl CloudSearchParams {
+    /// Convert to collection search parameters, mapping datasources to indexes
+    pub(crate) fn to_search_params(&self) -> SearchParams {
+        SearchParams {
+            term: self.term.clone(),
+            mode: self.mode.clone(),
+            limit: self.limit,
+            offset: self.offset,
+            properties: self.properties.clone(),
+            vector_properties: None,
+            where_clause: self.where_clause.clone(),
+            facets: self.facets.clone(),
+            indexes: Some(self.datasources.clone()),
+            datasource_ids: None,
+            exact: self.exact,
+            threshold: self.threshold,
+            tolerance: self.tolerance,
+            user_id: self.user_id.clone(),
+            boost: None,
+            hybrid_weights: None,
+            cursor: None,
+            return_properties: None,
+            auto_config: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// Drop hits beyond `limit_per_datasource` for each datasource
+    fn apply_limit_per_datasource<T>(&self, result: &mut SearchResult<T>) {
+        let Some(limit) = self.limit_per_datasource else {
+            return;
+        };
+
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        result.hits.retain(|hit| match &hit.datasource_id {
+            Some(datasource_id) => {
+                let count = seen.entry(datasource_id.clone()).or_default();
+                *count += 1;
+                *count <= limit
+            }
+            None => true,
+        });
+    }
+
     /// Create a new CloudSearchParams
     pub fn new<S: Into<String>>(term: S, datasources: Vec<String>) -> Self {
         Self {
@@ -259,4 +356,47 @@ impl CloudSearchParams {
         self.user_id = Some(user_id.into());
         self
     }
+
+    /// Keep at most `limit` hits from each datasource
+    pub fn with_limit_per_datasource(mut self, limit: usize) -> Self {
+        self.limit_per_datasource = Some(limit);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Hit;
+
+    #[test]
+    fn limit_per_datasource_keeps_the_first_hits_of_each_source() {
+        let hit = |id: &str, datasource_id: Option<&str>| Hit {
+            id: id.to_string(),
+            score: 1.0,
+            document: (),
+            datasource_id: datasource_id.map(String::from),
+        };
+        let mut result = SearchResult {
+            count: 5,
+            hits: vec![
+                hit("a", Some("docs")),
+                hit("b", Some("docs")),
+                hit("c", Some("blog")),
+                hit("d", Some("docs")),
+                hit("e", None),
+            ],
+            facets: None,
+            elapsed: None,
+            next_cursor: None,
+            resolved_mode: None,
+        };
+
+        let params = CloudSearchParams::new("q", vec!["docs".into(), "blog".into()])
+            .with_limit_per_datasource(1);
+        params.apply_limit_per_datasource(&mut result);
+
+        let ids: Vec<&str> = result.hits.iter().map(|h| h.id.as_str()).collect();
+        assert_eq!(ids, ["a", "c", "e"]);
+    }
 }
@@ -0,0 +1,25 @@
+//! Pluggable encoding of documents before they are written.
+
+use std::fmt;
+
+use crate::error::Result;
+
+/// Strategy for turning user documents into the JSON sent to the server.
+///
+/// Implement this to apply a transform, such as renaming keys, to every
+/// document written through an [`Index`](crate::collection::Index) without
+/// annotating each document type.
+pub trait DocumentCodec: fmt::Debug + Send + Sync {
+    /// Transform a document already serialized to JSON
+    fn transform(&self, document: serde_json::Value) -> Result<serde_json::Value>;
+}
+
+/// Default codec: documents are sent exactly as serde serializes them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl DocumentCodec for JsonCodec {
+    fn transform(&self, document: serde_json::Value) -> Result<serde_json::Value> {
+        Ok(document)
+    }
+}
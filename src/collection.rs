@@ -1,28 +1,82 @@
 //! Collection management and search functionality.
 
 use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use futures::stream::{Stream, StreamExt};
 use reqwest::Client;
+use reqwest_eventsource::Event;
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 use crate::auth::{ApiKeyAuth, Auth, AuthConfig, JwtAuth, Target};
-use crate::client::{ApiKeyPosition, ClientRequest, OramaClient};
-use crate::error::Result;
-use crate::stream_manager::OramaCoreStream;
+use crate::cache::{SearchCache, SearchCacheConfig};
+use crate::client::{ApiKeyPosition, ClientOptions, ClientRequest, OramaClient};
+use crate::codec::{DocumentCodec, JsonCodec};
+use crate::error::{OramaError, RequestContext, Result};
+use crate::filter::Filter;
+use crate::manager::{CollectionIndexField, GetCollectionsResponse};
+use crate::stream_manager::{AnswerConfig, CreateAiSessionConfig, OramaCoreStream, StreamChunk};
 use crate::types::*;
-use crate::utils::{current_time_millis, format_duration};
+use crate::utils::{format_duration, redact, PartitionedResults};
 
 const DEFAULT_READER_URL: &str = "https://collections.orama.com";
 const DEFAULT_JWT_URL: &str = "https://app.orama.com/api/user/jwt";
 
+/// Default number of documents sent per request when inserting from a stream
+pub const DEFAULT_INSERT_BATCH_SIZE: usize = 500;
+
+/// Default delay between recording an analytics event and sending its batch
+pub const DEFAULT_ANALYTICS_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Number of buffered analytics events that triggers an immediate flush
+const ANALYTICS_MAX_BATCH: usize = 100;
+
+/// Most analytics events kept while the endpoint is failing; older ones are dropped
+const ANALYTICS_MAX_PENDING: usize = 10 * ANALYTICS_MAX_BATCH;
+
 /// Configuration for CollectionManager
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct CollectionManagerConfig {
     pub collection_id: String,
     pub api_key: String,
     pub cluster: Option<ClusterConfig>,
     pub auth_jwt_url: Option<String>,
+    pub endpoints: Option<Endpoints>,
+    pub client_options: Option<ClientOptions>,
+    pub search_cache: Option<SearchCacheConfig>,
+    pub search_limits: Option<SearchLimits>,
+    pub analytics_flush_interval: Option<Duration>,
+    pub dry_run: bool,
+}
+
+impl fmt::Debug for CollectionManagerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CollectionManagerConfig")
+            .field("collection_id", &self.collection_id)
+            .field("api_key", &redact(&self.api_key))
+            .field("cluster", &self.cluster)
+            .field("auth_jwt_url", &self.auth_jwt_url)
+            .field("endpoints", &self.endpoints)
+            .field("client_options", &self.client_options)
+            .field("search_cache", &self.search_cache)
+            .field("search_limits", &self.search_limits)
+            .field("analytics_flush_interval", &self.analytics_flush_interval)
+            .field("dry_run", &self.dry_run)
+            .finish()
+    }
+}
+
+/// Default endpoints used by the client, overridable for self-hosted deployments
+#[derive(Debug, Clone)]
+pub struct Endpoints {
+    pub reader_url: String,
+    pub writer_url: Option<String>,
+    pub auth_jwt_url: String,
 }
 
 /// Cluster configuration
@@ -67,6 +121,120 @@ pub struct NewHookResponse {
     pub code: String,
 }
 
+/// Outcome of running a hook against a sample input
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HookTestResult {
+    /// Value returned by the hook
+    #[serde(default)]
+    pub output: Option<serde_json::Value>,
+    /// Lines logged by the hook while running
+    #[serde(default)]
+    pub logs: Vec<String>,
+    /// Error thrown by the hook, if it failed
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl HookTestResult {
+    /// Whether the hook ran without throwing
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Partial update for a single document
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentPatch {
+    pub id: String,
+    /// Only apply the patch if the document is still at this version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+    #[serde(flatten)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Report of a batched document insertion
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchInsertReport {
+    pub batches: usize,
+    pub documents: usize,
+}
+
+/// Report returned by write operations
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WriteReport {
+    /// Version of the written documents, when the server tracks versions
+    #[serde(default)]
+    pub version: Option<u64>,
+    /// Number of documents sent, or that would have been sent in a dry run
+    #[serde(skip)]
+    pub documents: usize,
+    /// Whether the write was only validated locally and never sent
+    #[serde(skip)]
+    pub dry_run: bool,
+}
+
+/// Report returned by delete operations
+#[derive(Debug, Default, Deserialize)]
+pub struct DeleteReport {
+    #[serde(default)]
+    pub deleted: u32,
+    /// IDs of the deleted documents, when reported by the server
+    #[serde(default)]
+    pub deleted_ids: Vec<String>,
+    /// Deleted document IDs per index, for deletes spanning several indexes
+    #[serde(skip)]
+    pub by_index: HashMap<String, Vec<String>>,
+    /// Error per index whose delete failed, for deletes spanning several indexes
+    #[serde(skip)]
+    pub failed: HashMap<String, OramaError>,
+}
+
+impl DeleteReport {
+    /// Whether the delete succeeded on every index
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Rerank request body
+#[derive(Debug, Clone, Serialize)]
+struct RerankBody<'a, T> {
+    query: &'a str,
+    documents: &'a [T],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_n: Option<u32>,
+}
+
+/// Score assigned by the rerank endpoint to an input document
+#[derive(Debug, Clone, Deserialize)]
+struct RerankScore {
+    index: usize,
+    score: f64,
+}
+
+/// Rerank endpoint response
+#[derive(Debug, Clone, Deserialize)]
+struct RerankResponse {
+    results: Vec<RerankScore>,
+}
+
+/// Snapshot of collection statistics
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CollectionStats {
+    #[serde(default)]
+    pub document_count: u64,
+    /// All other statistics returned by the server
+    #[serde(flatten)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Search response subset used by count-only searches
+#[derive(Debug, Clone, Deserialize)]
+struct CountResult {
+    count: u32,
+}
+
 /// Execute tools request body
 #[derive(Debug, Clone, Serialize)]
 pub struct ExecuteToolsBody {
@@ -102,15 +270,163 @@ impl AiNamespace {
             Target::Reader,
             ApiKeyPosition::QueryParams,
             params,
-        );
+        )
+        .with_json_repair(true);
 
         self.client.request(request).await
     }
 
+    /// Perform NLP-based search, streaming each step of the pipeline as it
+    /// completes
+    pub async fn nlp_search_stream<T>(
+        &self,
+        params: NlpSearchParams,
+    ) -> Result<impl Stream<Item = Result<NlpSearchStreamResult<T>>> + Send>
+    where
+        T: for<'de> serde::Deserialize<'de> + Send,
+    {
+        let path = format!("/v1/collections/{}/nlp_search_stream", self.collection_id);
+        let request = ClientRequest::post(
+            path.clone(),
+            Target::Reader,
+            ApiKeyPosition::QueryParams,
+            params,
+        );
+
+        let stream = self
+            .client
+            .event_stream(&request)
+            .await?
+            // Ends the stream on close, `[DONE]`, or right after an error, so
+            // the event source never reconnects
+            .scan(false, move |failed, event| {
+                if *failed {
+                    return futures::future::ready(None);
+                }
+
+                let item = match event {
+                    Ok(Event::Open) => None,
+                    Ok(Event::Message(message)) => match message.data.trim() {
+                        "[DONE]" => return futures::future::ready(None),
+                        "" => None,
+                        data => Some(
+                            serde_json::from_str::<NlpSearchStreamResult<T>>(data).map_err(|e| {
+                                OramaError::deserialize::<NlpSearchStreamResult<T>, _>(
+                                    data,
+                                    e.to_string(),
+                                )
+                            }),
+                        ),
+                    },
+                    Err(reqwest_eventsource::Error::StreamEnded) => {
+                        return futures::future::ready(None)
+                    }
+                    Err(reqwest_eventsource::Error::InvalidStatusCode(status)) => {
+                        *failed = true;
+                        Some(Err(OramaError::from_response(
+                            status.as_u16(),
+                            status.canonical_reason().unwrap_or_default(),
+                            Some(RequestContext::new("POST", path.as_str())),
+                        )))
+                    }
+                    Err(e) => {
+                        *failed = true;
+                        Some(Err(OramaError::stream(format!(
+                            "NLP search stream error: {e}"
+                        ))))
+                    }
+                };
+                futures::future::ready(Some(item))
+            })
+            .filter_map(futures::future::ready);
+
+        Ok(stream)
+    }
+
+    /// Rerank candidate documents by relevance to `query`.
+    ///
+    /// Returns the original documents, most relevant first, keeping at most
+    /// `top_n` of them when set.
+    pub async fn rerank<T>(
+        &self,
+        query: &str,
+        documents: Vec<T>,
+        top_n: Option<u32>,
+    ) -> Result<Vec<RankedHit<T>>>
+    where
+        T: Serialize,
+    {
+        let body = RerankBody {
+            query,
+            documents: &documents,
+            top_n,
+        };
+
+        let request = ClientRequest::post(
+            format!("/v1/collections/{}/rerank", self.collection_id),
+            Target::Reader,
+            ApiKeyPosition::QueryParams,
+            body,
+        );
+
+        let response: RerankResponse = self.client.request(request).await?;
+
+        let mut documents: Vec<Option<T>> = documents.into_iter().map(Some).collect();
+        let mut ranked = Vec::with_capacity(response.results.len());
+        for RerankScore { index, score } in response.results {
+            let document = documents
+                .get_mut(index)
+                .and_then(Option::take)
+                .ok_or_else(|| {
+                    OramaError::generic(format!(
+                        "Rerank response references unknown document index {index}"
+                    ))
+                })?;
+            ranked.push(RankedHit {
+                index,
+                score,
+                document,
+            });
+        }
+
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+        if let Some(top_n) = top_n {
+            ranked.truncate(top_n as usize);
+        }
+
+        Ok(ranked)
+    }
+
     /// Create an AI session for streaming conversations
     pub async fn create_ai_session(&self) -> Result<OramaCoreStream> {
         OramaCoreStream::new(self.collection_id.clone(), self.client.clone()).await
     }
+
+    /// Answer a single question without keeping any conversation history,
+    /// for stateless request handlers
+    pub async fn answer_once(&self, config: AnswerConfig) -> Result<String> {
+        self.one_shot_session().await?.answer(config).await
+    }
+
+    /// Stream the answer to a single question without keeping any
+    /// conversation history
+    pub async fn answer_once_stream(
+        &self,
+        config: AnswerConfig,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk>> + Send>>> {
+        self.one_shot_session().await?.answer_stream(config).await
+    }
+
+    /// Throwaway session that records no history; whatever it tracks is
+    /// dropped with it once the answer is done
+    async fn one_shot_session(&self) -> Result<OramaCoreStream> {
+        OramaCoreStream::with_config(
+            self.collection_id.clone(),
+            self.client.clone(),
+            CreateAiSessionConfig::new().with_track_history(false),
+        )
+        .await
+    }
 }
 
 /// Collections operations namespace
@@ -139,6 +455,43 @@ impl CollectionsNamespace {
         self.client.request(request).await
     }
 
+    /// Poll collection statistics every `interval`, yielding each snapshot.
+    ///
+    /// The first snapshot is fetched immediately. The stream never ends on its
+    /// own; errors are yielded and polling continues.
+    pub fn watch_stats(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<CollectionStats>> + '_ {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        futures::stream::unfold(ticker, move |mut ticker| async move {
+            ticker.tick().await;
+            let stats = self.get_stats().await.and_then(|value| {
+                serde_json::from_value::<CollectionStats>(value).map_err(OramaError::from)
+            });
+            Some((stats, ticker))
+        })
+    }
+
+    /// Like [`CollectionsNamespace::watch_stats`], but only yields a snapshot
+    /// when `document_count` differs from the previous one
+    pub fn watch_stats_changes(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<CollectionStats>> + '_ {
+        let mut last_count = None;
+
+        self.watch_stats(interval).filter(move |stats| {
+            let changed = match stats {
+                Ok(stats) => last_count.replace(stats.document_count) != Some(stats.document_count),
+                Err(_) => true,
+            };
+            futures::future::ready(changed)
+        })
+    }
+
     /// Get all documents in collection
     pub async fn get_all_docs<T>(&self, id: &str) -> Result<Vec<T>>
     where
@@ -161,16 +514,30 @@ impl CollectionsNamespace {
 pub struct IndexNamespace {
     client: OramaClient,
     collection_id: String,
+    search_cache: Option<Arc<SearchCache>>,
+    dry_run: bool,
 }
 
 impl IndexNamespace {
-    pub(crate) fn new(client: OramaClient, collection_id: String) -> Self {
+    pub(crate) fn new(
+        client: OramaClient,
+        collection_id: String,
+        search_cache: Option<Arc<SearchCache>>,
+    ) -> Self {
         Self {
             client,
             collection_id,
+            search_cache,
+            dry_run: false,
         }
     }
 
+    /// Hand out indexes in dry-run mode
+    pub(crate) fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
     /// Create a new index
     pub async fn create(&self, config: CreateIndexParams) -> Result<()> {
         let body = serde_json::json!({
@@ -206,9 +573,20 @@ impl IndexNamespace {
         Ok(())
     }
 
+    /// Get the field schema of an index
+    pub async fn get_fields(&self, index_id: &str) -> Result<Vec<CollectionIndexField>> {
+        self.set(index_id.to_string()).get_fields().await
+    }
+
     /// Get an Index instance for operations
     pub fn set(&self, id: String) -> Index {
-        Index::new(self.client.clone(), self.collection_id.clone(), id)
+        Index::new(
+            self.client.clone(),
+            self.collection_id.clone(),
+            id,
+            self.search_cache.clone(),
+        )
+        .with_dry_run(self.dry_run)
     }
 }
 
@@ -249,8 +627,9 @@ impl HooksNamespace {
         })
     }
 
-    /// List all hooks
-    pub async fn list(&self) -> Result<HashMap<String, Option<String>>> {
+    /// List all hooks with their code, including hooks unknown to this
+    /// client as [`Hook::Other`]
+    pub async fn list(&self) -> Result<HashMap<Hook, Option<String>>> {
         let request = ClientRequest::<()>::get(
             format!("/v1/collections/{}/hooks/list", self.collection_id),
             Target::Writer,
@@ -264,12 +643,49 @@ impl HooksNamespace {
         let mut result = HashMap::new();
         for (key, value) in hooks {
             let val = value.as_str().map(|s| s.to_string());
-            result.insert(key.clone(), val);
+            result.insert(Hook::from(key.as_str()), val);
         }
 
         Ok(result)
     }
 
+    /// Run the deployed code of a hook against a sample input without
+    /// affecting any search or answer
+    pub async fn test(&self, hook: Hook, input: serde_json::Value) -> Result<HookTestResult> {
+        let code = self
+            .list()
+            .await?
+            .remove(&hook)
+            .flatten()
+            .ok_or_else(|| OramaError::generic(format!("Hook {hook} is not set")))?;
+
+        self.test_code(hook, code, input).await
+    }
+
+    /// Run hook code against a sample input, e.g. to check it before deploying
+    /// it with [`insert`](Self::insert)
+    pub async fn test_code<S: Into<String>>(
+        &self,
+        hook: Hook,
+        code: S,
+        input: serde_json::Value,
+    ) -> Result<HookTestResult> {
+        let body = serde_json::json!({
+            "name": hook,
+            "code": code.into(),
+            "input": input
+        });
+
+        let request = ClientRequest::post(
+            format!("/v1/collections/{}/hooks/test", self.collection_id),
+            Target::Writer,
+            ApiKeyPosition::Header,
+            body,
+        );
+
+        self.client.request(request).await
+    }
+
     /// Delete a hook
     pub async fn delete(&self, hook: Hook) -> Result<()> {
         let body = serde_json::json!({
@@ -345,6 +761,20 @@ impl SystemPromptsNamespace {
         Ok(serde_json::from_value(prompts)?)
     }
 
+    /// Get all system prompts with the given usage mode
+    pub async fn get_all_by_mode(&self, mode: SystemPromptUsageMode) -> Result<Vec<SystemPrompt>> {
+        let prompts = self.get_all().await?;
+        Ok(prompts
+            .into_iter()
+            .filter(|prompt| prompt.usage_mode == mode)
+            .collect())
+    }
+
+    /// Count the system prompts in the collection
+    pub async fn count(&self) -> Result<usize> {
+        Ok(self.get_all().await?.len())
+    }
+
     /// Delete a system prompt
     pub async fn delete(&self, id: &str) -> Result<serde_json::Value> {
         let body = serde_json::json!({ "id": id });
@@ -425,6 +855,20 @@ impl ToolsNamespace {
         Ok(())
     }
 
+    /// Validate a tool definition before inserting it
+    pub async fn validate(&self, tool: InsertToolBody) -> Result<ToolValidationResponse> {
+        let request = ClientRequest::post(
+            format!("/v1/collections/{}/tools/validate", self.collection_id),
+            Target::Writer,
+            ApiKeyPosition::Header,
+            tool,
+        );
+
+        let response: serde_json::Value = self.client.request(request).await?;
+        let result = response["result"].clone();
+        Ok(serde_json::from_value(result)?)
+    }
+
     /// Get a tool
     pub async fn get(&self, id: &str) -> Result<Tool> {
         let request = ClientRequest::<()>::get(
@@ -487,30 +931,307 @@ impl ToolsNamespace {
             Target::Reader,
             ApiKeyPosition::QueryParams,
             tools,
-        );
+        )
+        .with_json_repair(true);
 
         self.client.request(request).await
     }
 }
 
+/// Analytics operations namespace.
+///
+/// Events are buffered and sent in batches, at most one flush interval after
+/// the first unsent event was recorded or as soon as the batch is full. Call
+/// [`flush`](Self::flush) before shutting down to send what is left.
+#[derive(Debug, Clone)]
+pub struct AnalyticsNamespace {
+    client: OramaClient,
+    collection_id: String,
+    pending: Arc<std::sync::Mutex<Vec<AnalyticsEvent>>>,
+    flush_interval: Duration,
+    flush_scheduled: Arc<AtomicBool>,
+    last_flush_failed: Arc<AtomicBool>,
+}
+
+impl AnalyticsNamespace {
+    pub(crate) fn new(
+        client: OramaClient,
+        collection_id: String,
+        flush_interval: Duration,
+    ) -> Self {
+        Self {
+            client,
+            collection_id,
+            pending: Arc::new(std::sync::Mutex::new(Vec::new())),
+            flush_interval,
+            flush_scheduled: Arc::new(AtomicBool::new(false)),
+            last_flush_failed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Record that a search was performed
+    pub async fn record_search_event<S: Into<String>>(
+        &self,
+        context: &AnalyticsContext,
+        query: S,
+        result_count: u32,
+    ) -> Result<()> {
+        self.record(AnalyticsEvent::new(
+            context,
+            AnalyticsEventKind::Search {
+                query: query.into(),
+                result_count,
+            },
+        ))
+        .await
+    }
+
+    /// Record a click on the hit at the zero-based `position`
+    pub async fn record_click<S: Into<String>>(
+        &self,
+        context: &AnalyticsContext,
+        hit_id: S,
+        position: usize,
+    ) -> Result<()> {
+        self.record(AnalyticsEvent::new(
+            context,
+            AnalyticsEventKind::Click {
+                hit_id: hit_id.into(),
+                position,
+            },
+        ))
+        .await
+    }
+
+    /// Record a conversion, optionally attributed to a hit
+    pub async fn record_conversion(
+        &self,
+        context: &AnalyticsContext,
+        hit_id: Option<String>,
+        value: Option<f64>,
+    ) -> Result<()> {
+        self.record(AnalyticsEvent::new(
+            context,
+            AnalyticsEventKind::Conversion { hit_id, value },
+        ))
+        .await
+    }
+
+    /// Buffer an event, sending the batch right away if it is full.
+    ///
+    /// Sending happens in the background, so a failing analytics endpoint is
+    /// logged rather than returned here.
+    pub async fn record(&self, event: AnalyticsEvent) -> Result<()> {
+        let batch_full = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.push(event);
+            Self::drop_oldest(&mut pending);
+            pending.len() >= ANALYTICS_MAX_BATCH
+        };
+
+        // While the endpoint is failing, retries wait for the scheduled flush
+        // instead of firing on every event
+        if batch_full && !self.last_flush_failed.load(Ordering::SeqCst) {
+            if let Err(e) = self.flush().await {
+                warn!("Failed to send analytics events: {}", e);
+            }
+            return Ok(());
+        }
+
+        // Later events ride along with the flush that is already scheduled,
+        // so steady traffic cannot keep postponing it
+        if !self.flush_scheduled.swap(true, Ordering::SeqCst) {
+            let analytics = self.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(analytics.flush_interval).await;
+                analytics.flush_scheduled.store(false, Ordering::SeqCst);
+                if let Err(e) = analytics.flush().await {
+                    warn!("Failed to send analytics events: {}", e);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Number of events waiting to be sent
+    pub fn pending_events(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    /// Send all buffered events now. On failure they are kept for the next
+    /// flush, up to a bound past which the oldest events are dropped.
+    pub async fn flush(&self) -> Result<()> {
+        let events = std::mem::take(&mut *self.pending.lock().unwrap());
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let request = ClientRequest::post(
+            format!("/v1/collections/{}/analytics/events", self.collection_id),
+            Target::Reader,
+            ApiKeyPosition::QueryParams,
+            serde_json::json!({ "events": &events }),
+        );
+
+        let result = self.client.request::<_, serde_json::Value>(request).await;
+        self.last_flush_failed
+            .store(result.is_err(), Ordering::SeqCst);
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                // Put the batch back ahead of anything recorded meanwhile
+                let mut pending = self.pending.lock().unwrap();
+                let newer = std::mem::replace(&mut *pending, events);
+                pending.extend(newer);
+                Self::drop_oldest(&mut pending);
+                Err(e)
+            }
+        }
+    }
+
+    fn drop_oldest(pending: &mut Vec<AnalyticsEvent>) {
+        if pending.len() > ANALYTICS_MAX_PENDING {
+            let dropped = pending.len() - ANALYTICS_MAX_PENDING;
+            pending.drain(..dropped);
+            warn!("Dropped {} unsent analytics events", dropped);
+        }
+    }
+}
+
 /// Index operations for document management
 #[derive(Debug, Clone)]
 pub struct Index {
     client: OramaClient,
     collection_id: String,
     index_id: String,
+    search_cache: Option<Arc<SearchCache>>,
+    codec: Arc<dyn DocumentCodec>,
+    dry_run: bool,
 }
 
 impl Index {
-    pub(crate) fn new(client: OramaClient, collection_id: String, index_id: String) -> Self {
+    pub(crate) fn new(
+        client: OramaClient,
+        collection_id: String,
+        index_id: String,
+        search_cache: Option<Arc<SearchCache>>,
+    ) -> Self {
         Self {
             client,
             collection_id,
             index_id,
+            search_cache,
+            codec: Arc::new(JsonCodec),
+            dry_run: false,
         }
     }
 
+    /// Encode written documents with a custom codec instead of plain JSON
+    pub fn with_codec<C: DocumentCodec + 'static>(mut self, codec: C) -> Self {
+        self.codec = Arc::new(codec);
+        self
+    }
+
+    /// Validate inserts, upserts and patches locally instead of sending them.
+    ///
+    /// Documents are still serialized and encoded, so payload errors surface,
+    /// but nothing is written and the returned [`WriteReport`] has `dry_run`
+    /// set. Deletes are not affected.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Send a document write, or only report it in dry-run mode
+    async fn send_write(
+        &self,
+        operation: &str,
+        body: serde_json::Value,
+        documents: usize,
+    ) -> Result<WriteReport> {
+        if self.dry_run {
+            info!(
+                "Dry run: skipped {} of {} documents into index {}",
+                operation, documents, self.index_id
+            );
+            return Ok(WriteReport {
+                documents,
+                dry_run: true,
+                ..WriteReport::default()
+            });
+        }
+
+        let request = ClientRequest::post(
+            format!(
+                "/v1/collections/{}/indexes/{}/documents/{operation}",
+                self.collection_id, self.index_id
+            ),
+            Target::Writer,
+            ApiKeyPosition::Header,
+            body,
+        );
+
+        // Some deployments answer writes with 204 No Content
+        let mut report = self
+            .client
+            .request::<_, Option<WriteReport>>(request)
+            .await?
+            .unwrap_or_default();
+        report.documents = documents;
+        self.invalidate_search_cache();
+        Ok(report)
+    }
+
+    /// Serialize documents and run them through the codec
+    fn encode_documents<T: Serialize>(&self, documents: Vec<T>) -> Result<Vec<serde_json::Value>> {
+        documents
+            .iter()
+            .map(|document| self.codec.transform(serde_json::to_value(document)?))
+            .collect()
+    }
+
+    /// Drop cached search results after a write
+    fn invalidate_search_cache(&self) {
+        if let Some(cache) = &self.search_cache {
+            cache.invalidate();
+        }
+    }
+
+    /// Fetch the field schema of this index and check a filter against it.
+    ///
+    /// Opt-in, since it costs a request; see [`Filter::validate_against`].
+    pub async fn validate_filter(&self, filter: &Filter) -> Result<()> {
+        filter.validate_against(&self.get_fields().await?)
+    }
+
+    /// Get the field schema of this index
+    pub async fn get_fields(&self) -> Result<Vec<CollectionIndexField>> {
+        let request = ClientRequest::<()>::get(
+            format!("/v1/collections/{}", self.collection_id),
+            Target::Writer,
+            ApiKeyPosition::Header,
+        );
+
+        let collection: GetCollectionsResponse = self.client.request(request).await?;
+
+        collection
+            .indexes
+            .into_iter()
+            .find(|index| index.id == self.index_id)
+            .map(|index| index.fields)
+            .ok_or_else(|| {
+                OramaError::generic(format!(
+                    "Index {} not found in collection {}",
+                    self.index_id, self.collection_id
+                ))
+            })
+    }
+
     /// Reindex the collection
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(collection_id = %self.collection_id, index_id = %self.index_id, operation = "reindex")
+    ))]
     pub async fn reindex(&self) -> Result<()> {
         let request = ClientRequest::<()>::post(
             format!(
@@ -526,31 +1247,81 @@ impl Index {
         Ok(())
     }
 
-    /// Insert documents
-    pub async fn insert_documents<T>(&self, documents: Vec<T>) -> Result<()>
+    /// Insert documents, returning what the server reported (or, in dry-run
+    /// mode, what would have been written)
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(collection_id = %self.collection_id, index_id = %self.index_id, operation = "insert_documents")
+    ))]
+    pub async fn insert_documents<T>(&self, documents: Vec<T>) -> Result<WriteReport>
     where
         T: Serialize,
     {
-        let body = serde_json::json!({
-            "documents": documents
-        });
+        let documents = self.encode_documents(documents)?;
+        let count = documents.len();
+        let body = serde_json::json!({ "documents": documents });
 
-        let request = ClientRequest::post(
-            format!(
-                "/v1/collections/{}/indexes/{}/documents/insert",
-                self.collection_id, self.index_id
-            ),
-            Target::Writer,
-            ApiKeyPosition::Header,
-            body,
-        );
+        self.send_write("insert", body, count).await
+    }
 
-        let _: serde_json::Value = self.client.request(request).await?;
-        Ok(())
+    /// Insert documents from an async stream, flushing in batches of
+    /// [`DEFAULT_INSERT_BATCH_SIZE`] documents
+    pub async fn insert_documents_stream<S, T>(&self, stream: S) -> Result<BatchInsertReport>
+    where
+        S: Stream<Item = T>,
+        T: Serialize,
+    {
+        self.insert_documents_stream_with_batch_size(stream, DEFAULT_INSERT_BATCH_SIZE)
+            .await
+    }
+
+    /// Insert documents from an async stream, flushing every `batch_size` documents
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(collection_id = %self.collection_id, index_id = %self.index_id, operation = "insert_documents_stream")
+    ))]
+    pub async fn insert_documents_stream_with_batch_size<S, T>(
+        &self,
+        stream: S,
+        batch_size: usize,
+    ) -> Result<BatchInsertReport>
+    where
+        S: Stream<Item = T>,
+        T: Serialize,
+    {
+        if batch_size == 0 {
+            return Err(OramaError::config("Batch size must be greater than zero"));
+        }
+
+        let mut report = BatchInsertReport::default();
+        let mut batches = std::pin::pin!(stream.chunks(batch_size));
+
+        while let Some(batch) = batches.next().await {
+            let batch_len = batch.len();
+            self.insert_documents(batch).await?;
+
+            report.batches += 1;
+            report.documents += batch_len;
+        }
+
+        Ok(report)
     }
 
     /// Delete documents
     pub async fn delete_documents(&self, document_ids: Vec<String>) -> Result<()> {
+        self.delete_documents_with_report(document_ids).await?;
+        Ok(())
+    }
+
+    /// Delete documents, returning what the server reports as deleted
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(collection_id = %self.collection_id, index_id = %self.index_id, operation = "delete_documents")
+    ))]
+    pub async fn delete_documents_with_report(
+        &self,
+        document_ids: Vec<String>,
+    ) -> Result<DeleteReport> {
         let body = serde_json::json!({
             "document_ids": document_ids
         });
@@ -565,22 +1336,52 @@ impl Index {
             body,
         );
 
-        let _: serde_json::Value = self.client.request(request).await?;
-        Ok(())
+        let report = self
+            .client
+            .request::<_, Option<DeleteReport>>(request)
+            .await?
+            .unwrap_or_default();
+        self.invalidate_search_cache();
+        Ok(report)
     }
 
-    /// Upsert documents
-    pub async fn upsert_documents<T>(&self, documents: Vec<T>) -> Result<()>
-    where
-        T: Serialize,
-    {
+    /// Delete every document matching a filter.
+    ///
+    /// An empty filter is rejected to avoid wiping the index by accident;
+    /// use [`Index::delete_all`] for that.
+    pub async fn delete_by_filter(&self, filter: AnyObject) -> Result<DeleteReport> {
+        let is_empty = match &filter {
+            serde_json::Value::Null => true,
+            serde_json::Value::Object(map) => map.is_empty(),
+            _ => false,
+        };
+
+        if is_empty {
+            return Err(OramaError::generic(
+                "Refusing to delete by an empty filter; use delete_all() to remove every document",
+            ));
+        }
+
+        self.send_delete_by_filter(filter).await
+    }
+
+    /// Delete every document in the index
+    pub async fn delete_all(&self) -> Result<DeleteReport> {
+        self.send_delete_by_filter(serde_json::json!({})).await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(collection_id = %self.collection_id, index_id = %self.index_id, operation = "delete_by_filter")
+    ))]
+    async fn send_delete_by_filter(&self, filter: AnyObject) -> Result<DeleteReport> {
         let body = serde_json::json!({
-            "documents": documents
+            "filter": filter
         });
 
         let request = ClientRequest::post(
             format!(
-                "/v1/collections/{}/indexes/{}/documents/upsert",
+                "/v1/collections/{}/indexes/{}/documents/delete_by_filter",
                 self.collection_id, self.index_id
             ),
             Target::Writer,
@@ -588,109 +1389,300 @@ impl Index {
             body,
         );
 
-        let _: serde_json::Value = self.client.request(request).await?;
-        Ok(())
+        let report = self
+            .client
+            .request::<_, Option<DeleteReport>>(request)
+            .await?
+            .unwrap_or_default();
+        self.invalidate_search_cache();
+        Ok(report)
+    }
+
+    /// Partially update documents, leaving unspecified fields untouched.
+    ///
+    /// Patches carrying a `version` fail with [`OramaError::Conflict`] when
+    /// the document has changed since.
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(collection_id = %self.collection_id, index_id = %self.index_id, operation = "patch_documents")
+    ))]
+    pub async fn patch_documents(&self, patches: Vec<DocumentPatch>) -> Result<WriteReport> {
+        if patches.iter().any(|patch| patch.id.is_empty()) {
+            return Err(OramaError::generic("Every document patch requires an id"));
+        }
+
+        let count = patches.len();
+        let body = serde_json::json!({
+            "documents": patches
+        });
+
+        self.send_write("patch", body, count).await
+    }
+
+    /// Upsert documents, returning the same report as [`insert_documents`](Self::insert_documents)
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(collection_id = %self.collection_id, index_id = %self.index_id, operation = "upsert_documents")
+    ))]
+    pub async fn upsert_documents<T>(&self, documents: Vec<T>) -> Result<WriteReport>
+    where
+        T: Serialize,
+    {
+        let documents = self.encode_documents(documents)?;
+        let count = documents.len();
+        let body = serde_json::json!({ "documents": documents });
+
+        self.send_write("upsert", body, count).await
+    }
+
+    /// Upsert documents only if they are still at `version`.
+    ///
+    /// Fails with [`OramaError::Conflict`] when a document has changed since.
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(collection_id = %self.collection_id, index_id = %self.index_id, operation = "upsert_documents_if_version")
+    ))]
+    pub async fn upsert_documents_if_version<T>(
+        &self,
+        documents: Vec<T>,
+        version: u64,
+    ) -> Result<WriteReport>
+    where
+        T: Serialize,
+    {
+        let documents = self.encode_documents(documents)?;
+        let count = documents.len();
+        let body = serde_json::json!({
+            "documents": documents,
+            "version": version
+        });
+
+        self.send_write("upsert", body, count).await
     }
 }
 
 /// Main collection manager
+///
+/// Cheap to clone and safe to share between tasks: clones share the HTTP
+/// connection pool, credentials and search cache.
 #[derive(Debug, Clone)]
 pub struct CollectionManager {
     client: OramaClient,
     collection_id: String,
+    search_cache: Option<Arc<SearchCache>>,
+    search_limits: SearchLimits,
     pub ai: AiNamespace,
     pub collections: CollectionsNamespace,
     pub index: IndexNamespace,
     pub hooks: HooksNamespace,
     pub system_prompts: SystemPromptsNamespace,
     pub tools: ToolsNamespace,
+    pub analytics: AnalyticsNamespace,
 }
 
 impl CollectionManager {
     /// Create a new CollectionManager
     pub async fn new(config: CollectionManagerConfig) -> Result<Self> {
+        let endpoints = config.resolved_endpoints();
+        endpoints.validate()?;
+
         let auth_config = if config.api_key.starts_with("p_") {
             // Private API Key (JWT flow)
-            AuthConfig::Jwt(
-                JwtAuth::new(
-                    config.auth_jwt_url.as_deref().unwrap_or(DEFAULT_JWT_URL),
-                    &config.collection_id,
-                    &config.api_key,
-                )
-                .with_reader_url(
-                    config
-                        .cluster
-                        .as_ref()
-                        .and_then(|c| c.read_url.as_deref())
-                        .unwrap_or(DEFAULT_READER_URL),
-                )
-                .with_writer_url(
-                    config
-                        .cluster
-                        .as_ref()
-                        .and_then(|c| c.writer_url.as_deref())
-                        .unwrap_or(""),
-                ),
+            let mut jwt_auth = JwtAuth::new(
+                endpoints.auth_jwt_url.as_str(),
+                &config.collection_id,
+                &config.api_key,
             )
+            .with_reader_url(endpoints.reader_url.as_str());
+            jwt_auth.writer_url = endpoints.writer_url;
+
+            AuthConfig::Jwt(jwt_auth)
         } else {
             // Regular API Key
-            AuthConfig::ApiKey(
-                ApiKeyAuth::new(&config.api_key)
-                    .with_reader_url(
-                        config
-                            .cluster
-                            .as_ref()
-                            .and_then(|c| c.read_url.as_deref())
-                            .unwrap_or(DEFAULT_READER_URL),
-                    )
-                    .with_writer_url(
-                        config
-                            .cluster
-                            .as_ref()
-                            .and_then(|c| c.writer_url.as_deref())
-                            .unwrap_or(""),
-                    ),
-            )
+            let mut api_key_auth =
+                ApiKeyAuth::new(&config.api_key).with_reader_url(endpoints.reader_url.as_str());
+            api_key_auth.writer_url = endpoints.writer_url;
+
+            AuthConfig::ApiKey(api_key_auth)
         };
 
-        let client = Client::new();
+        let client_options = config.client_options.clone().unwrap_or_default();
+        // The JWT endpoint may be self-hosted too, so it gets the same TLS setup
+        let client = client_options.apply_tls(Client::builder()).build()?;
         let auth = Auth::new(auth_config, Arc::new(client));
-        let orama_client = OramaClient::new(auth)?;
+        let orama_client = OramaClient::with_options(auth, client_options)?;
 
         let collection_id = config.collection_id.clone();
+        let search_cache = config
+            .search_cache
+            .clone()
+            .map(|cache_config| Arc::new(SearchCache::new(cache_config)));
 
         Ok(Self {
             ai: AiNamespace::new(orama_client.clone(), collection_id.clone()),
             collections: CollectionsNamespace::new(orama_client.clone(), collection_id.clone()),
-            index: IndexNamespace::new(orama_client.clone(), collection_id.clone()),
+            index: IndexNamespace::new(
+                orama_client.clone(),
+                collection_id.clone(),
+                search_cache.clone(),
+            )
+            .with_dry_run(config.dry_run),
             hooks: HooksNamespace::new(orama_client.clone(), collection_id.clone()),
             system_prompts: SystemPromptsNamespace::new(
                 orama_client.clone(),
                 collection_id.clone(),
             ),
             tools: ToolsNamespace::new(orama_client.clone(), collection_id.clone()),
+            analytics: AnalyticsNamespace::new(
+                orama_client.clone(),
+                collection_id.clone(),
+                config
+                    .analytics_flush_interval
+                    .unwrap_or(DEFAULT_ANALYTICS_FLUSH_INTERVAL),
+            ),
             client: orama_client,
             collection_id,
+            search_cache,
+            search_limits: config.search_limits.unwrap_or_default(),
         })
     }
 
+    /// Establish connections to the reader and writer hosts ahead of the first
+    /// real request, paying DNS and TLS setup up front.
+    ///
+    /// Best effort: failures are logged and otherwise ignored.
+    pub async fn warmup(&self) -> Result<()> {
+        let (reader, writer) = futures::join!(
+            self.client.warmup(Target::Reader),
+            self.client.warmup(Target::Writer)
+        );
+
+        for (target, result) in [(Target::Reader, reader), (Target::Writer, writer)] {
+            if let Err(e) = result {
+                warn!("Warmup of {:?} connection failed: {}", target, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// List the IDs of the collection's indexes
+    pub async fn indexes(&self) -> Result<Vec<String>> {
+        let request = ClientRequest::<()>::get(
+            format!("/v1/collections/{}", self.collection_id),
+            Target::Writer,
+            ApiKeyPosition::Header,
+        );
+
+        let collection: GetCollectionsResponse = self.client.request(request).await?;
+        Ok(collection
+            .indexes
+            .into_iter()
+            .map(|index| index.id)
+            .collect())
+    }
+
+    /// Get the collection's only index.
+    ///
+    /// Fails, listing the available indexes, when the collection has none or
+    /// more than one.
+    pub async fn default_index(&self) -> Result<Index> {
+        let mut indexes = self.indexes().await?;
+
+        match indexes.len() {
+            1 => Ok(self.index.set(indexes.remove(0))),
+            0 => Err(OramaError::generic(format!(
+                "Collection {} has no indexes",
+                self.collection_id
+            ))),
+            _ => Err(OramaError::generic(format!(
+                "Collection {} has multiple indexes ({}); select one with index.set",
+                self.collection_id,
+                indexes.join(", ")
+            ))),
+        }
+    }
+
+    /// Delete documents from every index of the collection, for when the
+    /// index holding each document is not known
+    pub async fn delete_documents_any_index(&self, ids: Vec<String>) -> Result<DeleteReport> {
+        let index_ids = self.indexes().await?;
+        self.delete_documents_in_indexes(ids, index_ids).await
+    }
+
+    /// Delete documents from each of the given indexes.
+    ///
+    /// `by_index` maps each index to the IDs the server reported deleting there.
+    /// A failure on one index does not abort the others: it is recorded in
+    /// `failed` and the report covers the indexes that succeeded. When every
+    /// index fails, the failure is returned as an error instead.
+    pub async fn delete_documents_in_indexes(
+        &self,
+        ids: Vec<String>,
+        index_ids: Vec<String>,
+    ) -> Result<DeleteReport> {
+        let deletes = index_ids.into_iter().map(|index_id| {
+            let index = self.index.set(index_id.clone());
+            let ids = ids.clone();
+            async move { (index_id, index.delete_documents_with_report(ids).await) }
+        });
+
+        let mut report = DeleteReport::default();
+        for (index_id, result) in futures::future::join_all(deletes).await {
+            let index_report = match result {
+                Ok(index_report) => index_report,
+                Err(e) => {
+                    report.failed.insert(index_id, e);
+                    continue;
+                }
+            };
+            report.deleted += index_report.deleted;
+            report
+                .deleted_ids
+                .extend(index_report.deleted_ids.iter().cloned());
+            report.by_index.insert(index_id, index_report.deleted_ids);
+        }
+
+        // Nothing succeeded, so there is no partial result to report
+        if report.by_index.is_empty() && !report.failed.is_empty() {
+            let mut failed: Vec<_> = report.failed.into_iter().collect();
+            if failed.len() == 1 {
+                return Err(failed.remove(0).1);
+            }
+            failed.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let failures: Vec<_> = failed
+                .iter()
+                .map(|(index_id, error)| format!("{index_id}: {error}"))
+                .collect();
+            return Err(OramaError::generic(format!(
+                "Delete failed on every index: {}",
+                failures.join("; ")
+            )));
+        }
+
+        Ok(report)
+    }
+
     /// Perform a search
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection_id = %self.collection_id, operation = "search")))]
     pub async fn search<T>(&self, query: &SearchParams) -> Result<SearchResult<T>>
     where
         T: for<'de> serde::Deserialize<'de>,
     {
-        let start_time = current_time_millis();
+        let query = query.for_request(&self.search_limits)?;
+        // Monotonic clock, so wall-clock adjustments cannot skew the timing
+        let start_time = Instant::now();
 
         let request = ClientRequest::post(
             format!("/v1/collections/{}/search", self.collection_id),
             Target::Reader,
             ApiKeyPosition::QueryParams,
-            query,
+            query.as_ref(),
         );
 
         let mut result: SearchResult<T> = self.client.request(request).await?;
 
-        let elapsed_time = current_time_millis() - start_time;
+        let elapsed_time = start_time.elapsed().as_millis() as u64;
         result.elapsed = Some(Elapsed {
             raw: elapsed_time,
             formatted: format_duration(elapsed_time),
@@ -698,6 +1690,157 @@ impl CollectionManager {
 
         Ok(result)
     }
+
+    /// Run several searches concurrently, splitting the results into
+    /// successes and failures
+    pub async fn search_many<T>(
+        &self,
+        queries: &[SearchParams],
+    ) -> PartitionedResults<SearchResult<T>>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let searches = queries.iter().map(|query| self.search::<T>(query));
+        futures::future::join_all(searches)
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    /// Create an index unless it already exists, and return its handle.
+    ///
+    /// A conflict or "already exists" error from the server counts as
+    /// success; any other error is returned. `config.id` is required, since a
+    /// server-generated id could not be looked up again.
+    pub async fn ensure_index(&self, config: CreateIndexParams) -> Result<Index> {
+        let index_id = config
+            .id
+            .clone()
+            .ok_or_else(|| OramaError::config("ensure_index requires an index id"))?;
+
+        match self.index.create(config).await {
+            Ok(()) => {}
+            Err(OramaError::Conflict { .. }) => {}
+            Err(OramaError::Api { message, .. })
+                if message.to_ascii_lowercase().contains("already exists") => {}
+            Err(e) => return Err(e),
+        }
+
+        Ok(self.index.set(index_id))
+    }
+
+    /// Fetch the page of results following `cursor`
+    pub async fn search_after<T>(
+        &self,
+        query: &SearchParams,
+        cursor: &str,
+    ) -> Result<SearchResult<T>>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let query = query.clone().with_cursor(cursor);
+        self.search(&query).await
+    }
+
+    /// Stream every page of a search, following `next_cursor` until the last
+    /// page, or until the server repeats a cursor or sends an empty one. Scales to large result sets where offset pagination does not.
+    pub fn search_pages<'a, T>(
+        &'a self,
+        query: &SearchParams,
+    ) -> impl Stream<Item = Result<SearchResult<T>>> + 'a
+    where
+        T: for<'de> serde::Deserialize<'de> + 'a,
+    {
+        let query = query.clone();
+        // `None` once the last page has been yielded or an error occurred
+        let initial = Some(query.cursor.clone());
+
+        futures::stream::unfold(initial, move |state| {
+            let query = query.clone();
+            async move {
+                let cursor = state?;
+                let result = match &cursor {
+                    Some(cursor) => self.search_after::<T>(&query, cursor).await,
+                    None => self.search::<T>(&query).await,
+                };
+
+                match result {
+                    Ok(page) => {
+                        // An empty or repeated cursor would fetch the same page forever
+                        let next = page
+                            .next_cursor
+                            .clone()
+                            .filter(|next| !next.is_empty() && cursor.as_ref() != Some(next))
+                            .map(Some);
+                        Some((Ok(page), next))
+                    }
+                    Err(e) => Some((Err(e), None)),
+                }
+            }
+        })
+    }
+
+    /// Perform a search and drop hits scoring below `min_score` locally
+    pub async fn search_min_score<T>(
+        &self,
+        query: &SearchParams,
+        min_score: f64,
+    ) -> Result<SearchResult<T>>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let mut result = self.search(query).await?;
+        result.filter_min_score(min_score);
+        Ok(result)
+    }
+
+    /// Perform a search, serving identical queries from the search cache.
+    ///
+    /// Falls back to [`CollectionManager::search`] when no cache is configured.
+    pub async fn search_cached<T>(&self, query: &SearchParams) -> Result<SearchResult<T>>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let Some(cache) = &self.search_cache else {
+            return self.search(query).await;
+        };
+
+        let key = cache.key(&self.collection_id, query)?;
+        if let Some(cached) = cache.get(key) {
+            return Ok(serde_json::from_value(cached)?);
+        }
+
+        let result: SearchResult<serde_json::Value> = self.search(query).await?;
+        let value = serde_json::to_value(&result)?;
+        cache.insert(key, value.clone());
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Get the search cache, if configured
+    pub fn search_cache(&self) -> Option<&SearchCache> {
+        self.search_cache.as_deref()
+    }
+
+    /// Count the documents matching a search without fetching any hits
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(collection_id = %self.collection_id, operation = "count")))]
+    pub async fn count(&self, query: &SearchParams) -> Result<u32> {
+        let query = SearchParams {
+            limit: Some(0),
+            offset: None,
+            ..query.for_request(&self.search_limits)?.into_owned()
+        };
+
+        let request = ClientRequest::post(
+            format!("/v1/collections/{}/search", self.collection_id),
+            Target::Reader,
+            ApiKeyPosition::QueryParams,
+            &query,
+        );
+
+        let result: CountResult = self.client.request(request).await?;
+        Ok(result.count)
+    }
 }
 
 // Builder implementations
@@ -709,6 +1852,12 @@ impl CollectionManagerConfig {
             api_key: api_key.into(),
             cluster: None,
             auth_jwt_url: None,
+            endpoints: None,
+            client_options: None,
+            search_cache: None,
+            search_limits: None,
+            analytics_flush_interval: None,
+            dry_run: false,
         }
     }
 
@@ -723,6 +1872,156 @@ impl CollectionManagerConfig {
         self.auth_jwt_url = Some(url.into());
         self
     }
+
+    /// Override the default endpoints
+    pub fn with_endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = Some(endpoints);
+        self
+    }
+
+    /// Set HTTP client options
+    pub fn with_client_options(mut self, options: ClientOptions) -> Self {
+        self.client_options = Some(options);
+        self
+    }
+
+    /// Enable the in-memory search result cache used by `search_cached`
+    pub fn with_search_cache(mut self, config: SearchCacheConfig) -> Self {
+        self.search_cache = Some(config);
+        self
+    }
+
+    /// Set the limits used to validate search parameters
+    pub fn with_search_limits(mut self, limits: SearchLimits) -> Self {
+        self.search_limits = Some(limits);
+        self
+    }
+
+    /// Set how long analytics events are buffered after the last one before
+    /// being sent
+    pub fn with_analytics_flush_interval(mut self, interval: Duration) -> Self {
+        self.analytics_flush_interval = Some(interval);
+        self
+    }
+
+    /// Validate document writes locally without sending them; see
+    /// [`Index::with_dry_run`]
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Resolve the effective endpoints.
+    ///
+    /// Explicit cluster URLs and the auth JWT URL take precedence over
+    /// `endpoints`, which in turn take precedence over the defaults.
+    pub(crate) fn resolved_endpoints(&self) -> Endpoints {
+        let mut endpoints = self.endpoints.clone().unwrap_or_default();
+
+        if let Some(cluster) = &self.cluster {
+            if let Some(read_url) = &cluster.read_url {
+                endpoints.reader_url = read_url.clone();
+            }
+            if let Some(writer_url) = &cluster.writer_url {
+                endpoints.writer_url = Some(writer_url.clone());
+            }
+        }
+
+        // An empty writer URL means "not configured"
+        endpoints.writer_url = endpoints.writer_url.filter(|url| !url.trim().is_empty());
+
+        if let Some(auth_jwt_url) = &self.auth_jwt_url {
+            endpoints.auth_jwt_url = auth_jwt_url.clone();
+        }
+
+        endpoints
+    }
+}
+
+impl Endpoints {
+    /// Create the default Orama Cloud endpoints
+    pub fn new() -> Self {
+        Self {
+            reader_url: DEFAULT_READER_URL.to_string(),
+            writer_url: None,
+            auth_jwt_url: DEFAULT_JWT_URL.to_string(),
+        }
+    }
+
+    /// Point every endpoint at a single base URL (self-hosted deployments)
+    pub fn from_base_url<S: Into<String>>(base_url: S) -> Self {
+        let base_url = base_url.into();
+        let trimmed = base_url.trim_end_matches('/');
+
+        Self {
+            reader_url: trimmed.to_string(),
+            writer_url: Some(trimmed.to_string()),
+            auth_jwt_url: format!("{trimmed}/api/user/jwt"),
+        }
+    }
+
+    /// Set reader URL
+    pub fn with_reader_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.reader_url = url.into();
+        self
+    }
+
+    /// Set writer URL
+    pub fn with_writer_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.writer_url = Some(url.into());
+        self
+    }
+
+    /// Set auth JWT URL
+    pub fn with_auth_jwt_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.auth_jwt_url = url.into();
+        self
+    }
+
+    /// Check that every configured endpoint is a valid URL
+    pub fn validate(&self) -> Result<()> {
+        fn check(field: &str, url: &str) -> Result<()> {
+            Url::parse(url)
+                .map(|_| ())
+                .map_err(|e| OramaError::config(format!("Invalid {field} \"{url}\": {e}")))
+        }
+
+        check("reader_url", &self.reader_url)?;
+        check("auth_jwt_url", &self.auth_jwt_url)?;
+        if let Some(writer_url) = self.writer_url.as_deref().filter(|u| !u.trim().is_empty()) {
+            check("writer_url", writer_url)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocumentPatch {
+    /// Create a new, empty patch for a document
+    pub fn new<S: Into<String>>(id: S) -> Self {
+        Self {
+            id: id.into(),
+            version: None,
+            fields: serde_json::Map::new(),
+        }
+    }
+
+    /// Only apply the patch if the document is still at `version`
+    pub fn with_version(mut self, version: u64) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Set a field to update
+    pub fn with_field<K: Into<String>>(mut self, key: K, value: serde_json::Value) -> Self {
+        self.fields.insert(key.into(), value);
+        self
+    }
 }
 
 impl ClusterConfig {
@@ -752,3 +2051,50 @@ impl Default for ClusterConfig {
         Self::new()
     }
 }
+
+impl NlpSearchParams {
+    /// Create new NLP search parameters
+    pub fn new<S: Into<String>>(query: S) -> Self {
+        Self {
+            query: query.into(),
+            llm_config: None,
+            user_id: None,
+        }
+    }
+
+    /// Set the LLM configuration
+    pub fn with_llm_config(mut self, llm_config: LlmConfig) -> Self {
+        self.llm_config = Some(llm_config);
+        self
+    }
+
+    /// Set user ID
+    pub fn with_user_id<S: Into<String>>(mut self, user_id: S) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nlp_search_params_builder() {
+        let params = NlpSearchParams::new("cheap shoes")
+            .with_llm_config(LlmConfig {
+                provider: LlmProvider::OpenAI,
+                model: "gpt-4o".to_string(),
+            })
+            .with_user_id("user-1");
+
+        assert_eq!(params.query, "cheap shoes");
+        assert_eq!(params.user_id.as_deref(), Some("user-1"));
+        let body = serde_json::to_value(&params).unwrap();
+        assert_eq!(body["LLMConfig"]["model"], "gpt-4o");
+        assert_eq!(body["userID"], "user-1");
+
+        let body = serde_json::to_value(NlpSearchParams::new("q")).unwrap();
+        assert_eq!(body, serde_json::json!({ "query": "q" }));
+    }
+}
@@ -1,12 +1,18 @@
 //! Error types for the Orama client.
 
+use std::fmt;
+
 use thiserror::Error;
 
 /// Result type alias for convenience
 pub type Result<T> = std::result::Result<T, OramaError>;
 
+/// Maximum number of body characters kept in a deserialization error
+const DESERIALIZE_SNIPPET_CHARS: usize = 256;
+
 /// Main error type for Orama operations
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum OramaError {
     /// HTTP client errors
     #[error("HTTP error: {0}")]
@@ -16,13 +22,35 @@ pub enum OramaError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// A response body could not be parsed into the expected type
+    #[error("Failed to parse response as {expected_type}: {message} (body: {raw_snippet})")]
+    Deserialize {
+        expected_type: String,
+        /// Start of the offending body, truncated to a few hundred characters
+        raw_snippet: String,
+        message: String,
+    },
+
     /// Authentication errors
     #[error("Authentication failed: {message}")]
     Auth { message: String },
 
     /// API errors returned from Orama
-    #[error("API error (status {status}): {message}")]
-    Api { status: u16, message: String },
+    #[error("API error (status {status}){}: {message}", display_context(context))]
+    Api {
+        status: u16,
+        message: String,
+        context: Option<RequestContext>,
+    },
+
+    /// A conditional write was rejected because the document changed
+    #[error("Write conflict{}: {message}", display_context(context))]
+    Conflict {
+        message: String,
+        /// Current version of the document, when reported by the server
+        current_version: Option<u64>,
+        context: Option<RequestContext>,
+    },
 
     /// Configuration errors
     #[error("Configuration error: {message}")]
@@ -45,6 +73,36 @@ pub enum OramaError {
     Generic { message: String },
 }
 
+/// The request that produced an error
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestContext {
+    pub method: String,
+    pub path: String,
+}
+
+impl RequestContext {
+    /// Create a new request context
+    pub fn new<M: Into<String>, P: Into<String>>(method: M, path: P) -> Self {
+        Self {
+            method: method.into(),
+            path: path.into(),
+        }
+    }
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.path)
+    }
+}
+
+fn display_context(context: &Option<RequestContext>) -> String {
+    context
+        .as_ref()
+        .map(|c| format!(" on {c}"))
+        .unwrap_or_default()
+}
+
 impl OramaError {
     /// Create a new authentication error
     pub fn auth<S: Into<String>>(message: S) -> Self {
@@ -58,6 +116,87 @@ impl OramaError {
         Self::Api {
             status,
             message: message.into(),
+            context: None,
+        }
+    }
+
+    /// Map a non-success response to the matching error variant.
+    ///
+    /// Shared by every request path so status codes are classified the same
+    /// way everywhere.
+    pub fn from_response<S: Into<String>>(
+        status: u16,
+        body: S,
+        context: Option<RequestContext>,
+    ) -> Self {
+        let body = body.into();
+
+        let error = match status {
+            401 => return Self::auth("Unauthorized: are you using the correct API Key?"),
+            409 => {
+                let current_version = serde_json::from_str::<serde_json::Value>(&body)
+                    .ok()
+                    .and_then(|body| body.get("current_version")?.as_u64());
+                Self::conflict(body, current_version)
+            }
+            400 => Self::api(status, format!("Bad Request: {body}")),
+            _ => Self::api(status, body),
+        };
+
+        match context {
+            Some(context) => error.with_context(context),
+            None => error,
+        }
+    }
+
+    /// Create a new write conflict error
+    pub fn conflict<S: Into<String>>(message: S, current_version: Option<u64>) -> Self {
+        Self::Conflict {
+            message: message.into(),
+            current_version,
+            context: None,
+        }
+    }
+
+    /// Attach request context to an API or conflict error; other variants
+    /// are returned unchanged
+    pub fn with_context(self, request_context: RequestContext) -> Self {
+        match self {
+            Self::Api {
+                status, message, ..
+            } => Self::Api {
+                status,
+                message,
+                context: Some(request_context),
+            },
+            Self::Conflict {
+                message,
+                current_version,
+                ..
+            } => Self::Conflict {
+                message,
+                current_version,
+                context: Some(request_context),
+            },
+            other => other,
+        }
+    }
+
+    /// Get the request context of an API or conflict error, if any
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            Self::Api { context, .. } | Self::Conflict { context, .. } => context.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Create a deserialization error for a body that did not parse as `T`
+    pub fn deserialize<T: ?Sized, S: Into<String>>(raw_body: &str, message: S) -> Self {
+        Self::Deserialize {
+            expected_type: std::any::type_name::<T>().to_string(),
+            raw_snippet: crate::utils::truncate_chars(raw_body, DESERIALIZE_SNIPPET_CHARS)
+                .to_string(),
+            message: message.into(),
         }
     }
 
@@ -82,3 +221,59 @@ impl OramaError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> Option<RequestContext> {
+        Some(RequestContext::new("POST", "/v1/collections/c/search"))
+    }
+
+    #[test]
+    fn unauthorized_maps_to_auth() {
+        let error = OramaError::from_response(401, "nope", context());
+
+        assert!(matches!(error, OramaError::Auth { .. }));
+    }
+
+    #[test]
+    fn conflict_keeps_the_current_version_and_context() {
+        let error = OramaError::from_response(409, r#"{"current_version":7}"#, context());
+
+        assert!(matches!(
+            &error,
+            OramaError::Conflict {
+                current_version: Some(7),
+                ..
+            }
+        ));
+        assert_eq!(error.context(), context().as_ref());
+        assert!(matches!(
+            OramaError::from_response(409, "not json", None),
+            OramaError::Conflict {
+                current_version: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn other_statuses_map_to_api_errors() {
+        for (status, body, message) in [
+            (400, "missing term", "Bad Request: missing term"),
+            (404, "not found", "not found"),
+            (429, "slow down", "slow down"),
+            (500, "boom", "boom"),
+            (503, "", ""),
+        ] {
+            let error = OramaError::from_response(status, body, context());
+
+            assert!(
+                matches!(&error, OramaError::Api { status: s, message: m, .. } if *s == status && m == message),
+                "{status}: {error:?}"
+            );
+            assert_eq!(error.context(), context().as_ref());
+        }
+    }
+}
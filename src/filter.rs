@@ -0,0 +1,304 @@
+//! Typed builder for search `where` clauses.
+
+use serde_json::{json, Map, Value};
+
+use crate::error::{OramaError, Result};
+use crate::manager::CollectionIndexField;
+
+/// Condition applied to a single field
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Condition {
+    Eq(Value),
+    Gt(f64),
+    Gte(f64),
+    Lt(f64),
+    Lte(f64),
+    Between(f64, f64),
+    In(Vec<Value>),
+    Nin(Vec<Value>),
+    ContainsAll(Vec<Value>),
+}
+
+impl Condition {
+    /// Name of the operator as used in the `where` clause
+    pub fn operator(&self) -> &'static str {
+        match self {
+            Self::Eq(_) => "eq",
+            Self::Gt(_) => "gt",
+            Self::Gte(_) => "gte",
+            Self::Lt(_) => "lt",
+            Self::Lte(_) => "lte",
+            Self::Between(..) => "between",
+            Self::In(_) => "in",
+            Self::Nin(_) => "nin",
+            Self::ContainsAll(_) => "containsAll",
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            // Strings and booleans are matched by value directly
+            Self::Eq(value @ (Value::String(_) | Value::Bool(_))) => value.clone(),
+            Self::Eq(value) => json!({ "eq": value }),
+            Self::Gt(n) | Self::Gte(n) | Self::Lt(n) | Self::Lte(n) => {
+                json!({ self.operator(): n })
+            }
+            Self::Between(from, to) => json!({ "between": [from, to] }),
+            Self::In(values) | Self::Nin(values) | Self::ContainsAll(values) => {
+                json!({ self.operator(): values })
+            }
+        }
+    }
+
+    fn is_range(&self) -> bool {
+        matches!(
+            self,
+            Self::Gt(_) | Self::Gte(_) | Self::Lt(_) | Self::Lte(_) | Self::Between(..)
+        )
+    }
+
+    /// Values compared against the field, for type checks
+    fn operands(&self) -> &[Value] {
+        match self {
+            Self::Eq(value) => std::slice::from_ref(value),
+            Self::In(values) | Self::Nin(values) | Self::ContainsAll(values) => values,
+            _ => &[],
+        }
+    }
+}
+
+/// Typed `where` clause, combining conditions on several fields with AND
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filter {
+    conditions: Vec<(String, Condition)>,
+}
+
+impl Filter {
+    /// Create an empty filter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a condition on a field
+    pub fn with<S: Into<String>>(mut self, field: S, condition: Condition) -> Self {
+        self.conditions.push((field.into(), condition));
+        self
+    }
+
+    /// Match documents whose field equals `value`
+    pub fn eq<S: Into<String>, V: Into<Value>>(self, field: S, value: V) -> Self {
+        self.with(field, Condition::Eq(value.into()))
+    }
+
+    /// Match documents whose field is greater than `value`
+    pub fn gt<S: Into<String>>(self, field: S, value: f64) -> Self {
+        self.with(field, Condition::Gt(value))
+    }
+
+    /// Match documents whose field is greater than or equal to `value`
+    pub fn gte<S: Into<String>>(self, field: S, value: f64) -> Self {
+        self.with(field, Condition::Gte(value))
+    }
+
+    /// Match documents whose field is less than `value`
+    pub fn lt<S: Into<String>>(self, field: S, value: f64) -> Self {
+        self.with(field, Condition::Lt(value))
+    }
+
+    /// Match documents whose field is less than or equal to `value`
+    pub fn lte<S: Into<String>>(self, field: S, value: f64) -> Self {
+        self.with(field, Condition::Lte(value))
+    }
+
+    /// Match documents whose field lies between `from` and `to`, inclusive
+    pub fn between<S: Into<String>>(self, field: S, from: f64, to: f64) -> Self {
+        self.with(field, Condition::Between(from, to))
+    }
+
+    /// Match documents whose field is one of `values`
+    pub fn any_of<S: Into<String>, V: Into<Value>>(self, field: S, values: Vec<V>) -> Self {
+        self.with(
+            field,
+            Condition::In(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// Match documents whose field is none of `values`
+    pub fn none_of<S: Into<String>, V: Into<Value>>(self, field: S, values: Vec<V>) -> Self {
+        self.with(
+            field,
+            Condition::Nin(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// Match documents whose array field contains all of `values`
+    pub fn contains_all<S: Into<String>, V: Into<Value>>(self, field: S, values: Vec<V>) -> Self {
+        self.with(
+            field,
+            Condition::ContainsAll(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// The conditions of this filter
+    pub fn conditions(&self) -> &[(String, Condition)] {
+        &self.conditions
+    }
+
+    /// Build the JSON `where` clause.
+    ///
+    /// Operators on the same field are merged into one object, so
+    /// `gte("price", 10.0).lte("price", 50.0)` becomes
+    /// `{"price": {"gte": 10.0, "lte": 50.0}}`. A repeated operator, or a
+    /// string or boolean equality that cannot share an object, keeps the
+    /// last condition.
+    pub fn to_where(&self) -> Value {
+        let mut clause = Map::new();
+        for (field, condition) in &self.conditions {
+            let value = condition.to_value();
+            match (clause.get_mut(field), value) {
+                (Some(Value::Object(existing)), Value::Object(operators)) => {
+                    existing.extend(operators);
+                }
+                (_, value) => {
+                    clause.insert(field.clone(), value);
+                }
+            }
+        }
+        Value::Object(clause)
+    }
+
+    /// Check each condition against the index schema, e.g. from
+    /// [`Index::get_fields`](crate::collection::Index::get_fields).
+    ///
+    /// Rejects unknown fields, range operators on non-numeric fields,
+    /// `containsAll` on non-array fields and values of the wrong type.
+    /// Fields of a type this client does not recognize are not checked.
+    pub fn validate_against(&self, fields: &[CollectionIndexField]) -> Result<()> {
+        for (name, condition) in &self.conditions {
+            let field = fields
+                .iter()
+                .find(|field| field.field_path == *name)
+                .ok_or_else(|| OramaError::generic(format!("Unknown filter field \"{name}\"")))?;
+
+            let mismatch = |problem: String| {
+                OramaError::generic(format!(
+                    "Operator \"{}\" on field \"{name}\" {problem}, but the field is {}",
+                    condition.operator(),
+                    field.type_name().unwrap_or("of an unknown type")
+                ))
+            };
+
+            if matches!(condition, Condition::ContainsAll(_)) && !field.is_array {
+                return Err(mismatch("needs an array field".to_string()));
+            }
+
+            let known_type =
+                field.is_number() || field.is_text() || field.is_bool() || field.is_vector();
+            if !known_type {
+                continue;
+            }
+
+            if condition.is_range() && !field.is_number() {
+                return Err(mismatch("needs a number field".to_string()));
+            }
+
+            for operand in condition.operands() {
+                let matches_type = match operand {
+                    Value::Number(_) => field.is_number(),
+                    Value::String(_) => field.is_text(),
+                    Value::Bool(_) => field.is_bool(),
+                    _ => true,
+                };
+                if !matches_type {
+                    return Err(mismatch(format!("has a {} value", json_type_name(operand))));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<Filter> for Value {
+    fn from(filter: Filter) -> Self {
+        filter.to_where()
+    }
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Bool(_) => "boolean",
+        _ => "non-scalar",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> Vec<CollectionIndexField> {
+        serde_json::from_value(json!([
+            { "field_id": "1", "field_path": "title", "is_array": false, "field_type": "String" },
+            { "field_id": "2", "field_path": "price", "is_array": false, "field_type": { "Number": {} } },
+            { "field_id": "3", "field_path": "tags", "is_array": true, "field_type": "String" },
+            { "field_id": "4", "field_path": "in_stock", "is_array": false, "field_type": "Bool" },
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn matching_filter_is_valid() {
+        let filter = Filter::new()
+            .eq("title", "shoes")
+            .between("price", 10.0, 50.0)
+            .contains_all("tags", vec!["red"])
+            .eq("in_stock", true);
+
+        assert!(filter.validate_against(&fields()).is_ok());
+    }
+
+    #[test]
+    fn type_mismatches_are_rejected() {
+        let error = Filter::new()
+            .gt("title", 3.0)
+            .validate_against(&fields())
+            .unwrap_err();
+        assert!(error.to_string().contains("needs a number field"));
+
+        let error = Filter::new()
+            .contains_all("price", vec![1])
+            .validate_against(&fields())
+            .unwrap_err();
+        assert!(error.to_string().contains("needs an array field"));
+
+        let error = Filter::new()
+            .eq("price", "cheap")
+            .validate_against(&fields())
+            .unwrap_err();
+        assert!(error.to_string().contains("has a string value"));
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let error = Filter::new()
+            .eq("color", "red")
+            .validate_against(&fields())
+            .unwrap_err();
+        assert!(error.to_string().contains("Unknown filter field \"color\""));
+    }
+
+    #[test]
+    fn two_sided_range_keeps_both_bounds() {
+        let filter = Filter::new()
+            .gte("price", 10.0)
+            .lte("price", 50.0)
+            .eq("title", "shoes");
+
+        assert_eq!(
+            filter.to_where(),
+            json!({ "price": { "gte": 10.0, "lte": 50.0 }, "title": "shoes" })
+        );
+    }
+}
@@ -31,11 +31,17 @@
 //! }
 //! ```
 
+#[macro_use]
+mod logging;
+
 pub mod auth;
+pub mod cache;
 pub mod client;
 pub mod cloud;
+pub mod codec;
 pub mod collection;
 pub mod error;
+pub mod filter;
 pub mod manager;
 pub mod stream_manager;
 pub mod types;
@@ -48,3 +54,20 @@ pub use error::{OramaError, Result};
 pub use manager::OramaCoreManager;
 pub use stream_manager::OramaCoreStream;
 pub use types::*;
+
+// The clients are meant to live in shared application state: fail the build
+// if any of them stops being safe to share across tasks
+const _: () = {
+    fn assert_send_sync<T: Send + Sync>() {}
+    fn assert_clone<T: Clone>() {}
+
+    #[allow(dead_code)]
+    fn assert_shareable() {
+        assert_send_sync::<CollectionManager>();
+        assert_send_sync::<OramaCloud>();
+        assert_send_sync::<OramaCoreStream>();
+        assert_send_sync::<OramaCoreManager>();
+        assert_clone::<CollectionManager>();
+        assert_clone::<OramaCloud>();
+    }
+};
@@ -0,0 +1,46 @@
+//! Log macros that forward to `tracing` and compile to nothing without the
+//! `tracing` feature.
+//!
+//! Declared with `#[macro_use]` ahead of the other modules, so they are in
+//! scope crate-wide without imports.
+
+#[cfg(feature = "tracing")]
+macro_rules! log_event {
+    ($level:ident, $($arg:tt)+) => {
+        ::tracing::$level!($($arg)+)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! log_event {
+    ($level:ident, $($arg:tt)+) => {
+        // Keep the arguments type-checked and used
+        if false {
+            let _ = ::std::format!($($arg)+);
+        }
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        log_event!(debug, $($arg)+)
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)+) => {
+        log_event!(info, $($arg)+)
+    };
+}
+
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        log_event!(warn, $($arg)+)
+    };
+}
+
+macro_rules! error {
+    ($($arg:tt)+) => {
+        log_event!(error, $($arg)+)
+    };
+}
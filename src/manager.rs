@@ -1,5 +1,7 @@
 //! Orama Core Manager for collection management operations.
 
+use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
 
 use reqwest::Client;
@@ -7,17 +9,27 @@ use serde::{Deserialize, Serialize};
 
 use crate::auth::{ApiKeyAuth, Auth, AuthConfig, Target};
 use crate::client::{ApiKeyPosition, ClientRequest, OramaClient};
-use crate::error::Result;
+use crate::collection::{CreateIndexParams, Index, IndexNamespace};
+use crate::error::{OramaError, Result};
 use crate::types::*;
-use crate::utils::create_random_string;
+use crate::utils::{create_random_string, generate_uuid, redact};
 
 /// Configuration for OramaCoreManager
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct OramaCoreManagerConfig {
     pub url: String,
     pub master_api_key: String,
 }
 
+impl fmt::Debug for OramaCoreManagerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OramaCoreManagerConfig")
+            .field("url", &self.url)
+            .field("master_api_key", &redact(&self.master_api_key))
+            .finish()
+    }
+}
+
 /// Parameters for creating a collection
 #[derive(Debug, Clone, Serialize)]
 pub struct CreateCollectionParams {
@@ -32,17 +44,45 @@ pub struct CreateCollectionParams {
     pub language: Option<Language>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub embeddings_model: Option<EmbeddingsModel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeddings: Option<EmbeddingsConfig>,
 }
 
 /// Response from creating a new collection
 #[derive(Debug, Clone, Deserialize)]
 pub struct NewCollectionResponse {
+    #[serde(default)]
     pub id: String,
+    #[serde(default)]
     pub description: Option<String>,
-    #[serde(rename = "writeAPIKey")]
+    #[serde(rename = "writeAPIKey", alias = "write_api_key", default)]
     pub write_api_key: String,
-    #[serde(rename = "readonlyAPIKey")]
+    #[serde(rename = "readonlyAPIKey", alias = "read_api_key", default)]
     pub readonly_api_key: String,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub default_index_id: Option<String>,
+    /// Any other fields returned by the server
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl NewCollectionResponse {
+    /// Ensure the API keys needed to use the collection are present
+    fn validate(self) -> Result<Self> {
+        if self.write_api_key.is_empty() {
+            return Err(OramaError::generic(
+                "Create collection response is missing the write API key",
+            ));
+        }
+        if self.readonly_api_key.is_empty() {
+            return Err(OramaError::generic(
+                "Create collection response is missing the read API key",
+            ));
+        }
+        Ok(self)
+    }
 }
 
 /// Collection index field information
@@ -54,15 +94,104 @@ pub struct CollectionIndexField {
     pub field_type: serde_json::Value,
 }
 
+impl CollectionIndexField {
+    /// The type name of the field, e.g. `"String"` for `"String"` or
+    /// `{"String": {...}}`
+    pub fn type_name(&self) -> Option<&str> {
+        match &self.field_type {
+            serde_json::Value::String(name) => Some(name),
+            serde_json::Value::Object(map) => map.keys().next().map(String::as_str),
+            _ => None,
+        }
+    }
+
+    /// Whether the field is a full-text field
+    pub fn is_text(&self) -> bool {
+        self.type_name().is_some_and(|name| {
+            let name = name.to_lowercase();
+            name.contains("string") || name.contains("text")
+        })
+    }
+
+    /// Whether the field is numeric
+    pub fn is_number(&self) -> bool {
+        self.type_name().is_some_and(|name| {
+            let name = name.to_lowercase();
+            name.contains("number") || name.contains("integer") || name.contains("float")
+        })
+    }
+
+    /// Whether the field is a boolean
+    pub fn is_bool(&self) -> bool {
+        self.type_name()
+            .is_some_and(|name| name.to_lowercase().contains("bool"))
+    }
+
+    /// Whether the field holds embeddings
+    pub fn is_vector(&self) -> bool {
+        self.type_name().is_some_and(|name| {
+            let name = name.to_lowercase();
+            name.contains("vector") || name.contains("embedding")
+        })
+    }
+}
+
+/// Property that Orama selected automatically for an index
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChosenProperty {
+    pub name: String,
+    /// Why or how the property was selected, when reported
+    pub reason: Option<String>,
+}
+
 /// Collection index information
 #[derive(Debug, Clone, Deserialize)]
 pub struct CollectionIndex {
     pub id: String,
     pub document_count: u32,
     pub fields: Vec<CollectionIndexField>,
+    /// Raw selection as returned by the server; see [`CollectionIndex::chosen_properties`]
+    #[serde(default)]
     pub automatically_chosen_properties: serde_json::Value,
 }
 
+impl CollectionIndex {
+    /// The automatically chosen properties, accepting either a list of names
+    /// or a map of name to reason (a string, or an object with a `reason` or
+    /// `type` field)
+    pub fn chosen_properties(&self) -> Vec<ChosenProperty> {
+        match &self.automatically_chosen_properties {
+            serde_json::Value::Array(names) => names
+                .iter()
+                .filter_map(|name| name.as_str())
+                .map(|name| ChosenProperty {
+                    name: name.to_string(),
+                    reason: None,
+                })
+                .collect(),
+            serde_json::Value::Object(map) => map
+                .iter()
+                .map(|(name, detail)| {
+                    let reason = match detail {
+                        serde_json::Value::String(reason) => Some(reason.clone()),
+                        serde_json::Value::Object(detail) => detail
+                            .get("reason")
+                            .or_else(|| detail.get("type"))
+                            .and_then(|reason| reason.as_str())
+                            .map(String::from),
+                        _ => None,
+                    };
+                    ChosenProperty {
+                        name: name.clone(),
+                        reason,
+                    }
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
 /// Response from getting collections
 #[derive(Debug, Clone, Deserialize)]
 pub struct GetCollectionsResponse {
@@ -72,6 +201,15 @@ pub struct GetCollectionsResponse {
     pub indexes: Vec<CollectionIndex>,
 }
 
+/// Outcome of [`CollectionNamespace::create_if_absent`]
+#[derive(Debug, Clone)]
+pub enum CollectionCreation {
+    /// The collection already existed
+    Existing(GetCollectionsResponse),
+    /// The collection was newly created
+    Created(NewCollectionResponse),
+}
+
 /// Collection management namespace
 #[derive(Debug, Clone)]
 pub struct CollectionNamespace {
@@ -93,8 +231,20 @@ impl CollectionNamespace {
             "read_api_key": config.read_api_key.unwrap_or_else(|| create_random_string(32)),
         });
 
-        if let Some(embeddings_model) = config.embeddings_model {
-            body["embeddings_model"] = serde_json::to_value(embeddings_model)?;
+        // The model is sent once: inside a full embeddings config when one is
+        // set, otherwise through the `embeddings_model` shortcut
+        match config.embeddings {
+            Some(mut embeddings) => {
+                if embeddings.model.is_none() {
+                    embeddings.model = config.embeddings_model;
+                }
+                body["embeddings"] = serde_json::to_value(embeddings)?;
+            }
+            None => {
+                if let Some(embeddings_model) = config.embeddings_model {
+                    body["embeddings_model"] = serde_json::to_value(embeddings_model)?;
+                }
+            }
         }
 
         let request = ClientRequest::post(
@@ -104,21 +254,8 @@ impl CollectionNamespace {
             body,
         );
 
-        let response: serde_json::Value = self.client.request(request).await?;
-
-        // Convert response to NewCollectionResponse
-        Ok(NewCollectionResponse {
-            id: response["id"].as_str().unwrap_or_default().to_string(),
-            description: response["description"].as_str().map(|s| s.to_string()),
-            write_api_key: response["write_api_key"]
-                .as_str()
-                .unwrap_or_default()
-                .to_string(),
-            readonly_api_key: response["read_api_key"]
-                .as_str()
-                .unwrap_or_default()
-                .to_string(),
-        })
+        let response: NewCollectionResponse = self.client.request(request).await?;
+        response.validate()
     }
 
     /// List all collections
@@ -143,6 +280,29 @@ impl CollectionNamespace {
         self.client.request(request).await
     }
 
+    /// Check whether a collection exists
+    pub async fn exists(&self, collection_id: &str) -> Result<bool> {
+        match self.get(collection_id).await {
+            Ok(_) => Ok(true),
+            Err(OramaError::Api { status: 404, .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Create a collection unless one with the same id already exists
+    pub async fn create_if_absent(
+        &self,
+        config: CreateCollectionParams,
+    ) -> Result<CollectionCreation> {
+        match self.get(&config.id).await {
+            Ok(existing) => Ok(CollectionCreation::Existing(existing)),
+            Err(OramaError::Api { status: 404, .. }) => {
+                Ok(CollectionCreation::Created(self.create(config).await?))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Delete a collection
     pub async fn delete(&self, collection_id: &str) -> Result<()> {
         let body = serde_json::json!({
@@ -165,13 +325,14 @@ impl CollectionNamespace {
 #[derive(Debug, Clone)]
 pub struct OramaCoreManager {
     pub collection: CollectionNamespace,
+    url: String,
 }
 
 impl OramaCoreManager {
     /// Create a new OramaCoreManager
     pub async fn new(config: OramaCoreManagerConfig) -> Result<Self> {
         let auth_config =
-            AuthConfig::ApiKey(ApiKeyAuth::new(config.master_api_key).with_writer_url(config.url));
+            AuthConfig::ApiKey(ApiKeyAuth::new(config.master_api_key).with_writer_url(&config.url));
 
         let client = Client::new();
         let auth = Auth::new(auth_config, Arc::new(client));
@@ -179,8 +340,43 @@ impl OramaCoreManager {
 
         Ok(Self {
             collection: CollectionNamespace::new(orama_client),
+            url: config.url,
         })
     }
+
+    /// Create a collection together with an index, returning an [`Index`]
+    /// authenticated with the new collection's write key.
+    ///
+    /// If the index cannot be created, the collection is deleted again so no
+    /// half-initialized collection is left behind.
+    pub async fn create_collection_with_index(
+        &self,
+        collection_params: CreateCollectionParams,
+        mut index_params: CreateIndexParams,
+    ) -> Result<(NewCollectionResponse, Index)> {
+        let collection = self.collection.create(collection_params).await?;
+
+        let index_id = index_params.id.get_or_insert_with(generate_uuid).clone();
+
+        let auth_config = AuthConfig::ApiKey(
+            ApiKeyAuth::new(collection.write_api_key.as_str()).with_writer_url(&self.url),
+        );
+        let auth = Auth::new(auth_config, Arc::new(Client::new()));
+        let indexes = IndexNamespace::new(OramaClient::new(auth)?, collection.id.clone(), None);
+
+        if let Err(e) = indexes.create(index_params).await {
+            if let Err(cleanup) = self.collection.delete(&collection.id).await {
+                warn!(
+                    "Failed to delete collection {} after index creation failed: {}",
+                    collection.id, cleanup
+                );
+            }
+            return Err(e);
+        }
+
+        let index = indexes.set(index_id);
+        Ok((collection, index))
+    }
 }
 
 impl CreateCollectionParams {
@@ -193,6 +389,7 @@ impl CreateCollectionParams {
             read_api_key: None,
             language: None,
             embeddings_model: None,
+            embeddings: None,
         }
     }
 
@@ -225,4 +422,127 @@ impl CreateCollectionParams {
         self.embeddings_model = Some(model);
         self
     }
+
+    /// Set the full embeddings configuration (model and document fields)
+    pub fn with_embeddings_config(mut self, config: EmbeddingsConfig) -> Self {
+        self.embeddings = Some(config);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_fields_deserialize_with_their_types() {
+        let index: CollectionIndex = serde_json::from_value(serde_json::json!({
+            "id": "products",
+            "document_count": 2,
+            "fields": [
+                { "field_id": "1", "field_path": "title", "is_array": false, "field_type": { "String": { "language": "english" } } },
+                { "field_id": "2", "field_path": "price", "is_array": false, "field_type": "Number" },
+                { "field_id": "3", "field_path": "in_stock", "is_array": false, "field_type": "Bool" },
+                { "field_id": "4", "field_path": "embedding", "is_array": true, "field_type": { "Embedding": { "model": "BGESmall" } } }
+            ]
+        }))
+        .unwrap();
+
+        let kinds: Vec<_> = index
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.field_path.as_str(),
+                    field.type_name(),
+                    field.is_text(),
+                    field.is_number(),
+                    field.is_bool(),
+                    field.is_vector(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("title", Some("String"), true, false, false, false),
+                ("price", Some("Number"), false, true, false, false),
+                ("in_stock", Some("Bool"), false, false, true, false),
+                ("embedding", Some("Embedding"), false, false, false, true),
+            ]
+        );
+        assert!(index.fields[3].is_array);
+    }
+
+    #[test]
+    fn create_response_without_keys_is_rejected() {
+        let response: NewCollectionResponse = serde_json::from_value(serde_json::json!({
+            "id": "col",
+            "writeAPIKey": "write"
+        }))
+        .unwrap();
+        assert!(response.validate().is_err());
+
+        let response: NewCollectionResponse = serde_json::from_value(serde_json::json!({
+            "id": "col",
+            "write_api_key": "write",
+            "read_api_key": "read"
+        }))
+        .unwrap();
+        let response = response.validate().unwrap();
+        assert_eq!(response.write_api_key, "write");
+        assert_eq!(response.readonly_api_key, "read");
+    }
+
+    #[test]
+    fn automatically_chosen_properties_deserialize() {
+        let parse = |chosen: serde_json::Value| {
+            let index: CollectionIndex = serde_json::from_value(serde_json::json!({
+                "id": "products",
+                "document_count": 0,
+                "fields": [],
+                "automatically_chosen_properties": chosen
+            }))
+            .unwrap();
+            index.chosen_properties()
+        };
+        let property = |name: &str, reason: Option<&str>| ChosenProperty {
+            name: name.to_string(),
+            reason: reason.map(String::from),
+        };
+
+        assert_eq!(
+            parse(serde_json::json!(["title", "description"])),
+            [property("title", None), property("description", None)]
+        );
+        let mut chosen = parse(serde_json::json!({
+            "title": "text",
+            "tags": { "reason": "array of strings" },
+            "price": { "type": "number" },
+            "other": 3
+        }));
+        chosen.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(
+            chosen,
+            [
+                property("other", None),
+                property("price", Some("number")),
+                property("tags", Some("array of strings")),
+                property("title", Some("text")),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_chosen_properties_default_to_empty() {
+        let index: CollectionIndex = serde_json::from_value(serde_json::json!({
+            "id": "products",
+            "document_count": 0,
+            "fields": []
+        }))
+        .unwrap();
+
+        assert!(index.automatically_chosen_properties.is_null());
+        assert!(index.chosen_properties().is_empty());
+    }
 }
@@ -1,23 +1,25 @@
 //! AI session streaming functionality.
 
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use futures::future;
 use futures::stream::{Stream, StreamExt};
-use reqwest_eventsource::{Event, EventSource};
+use reqwest_eventsource::Event;
 use serde::Serialize;
 use tokio::sync::RwLock;
-use tracing::{debug, error, info, warn};
 
 use crate::auth::Target;
 use crate::client::{ApiKeyPosition, ClientRequest, OramaClient};
-use crate::error::{OramaError, Result};
+use crate::error::{OramaError, RequestContext, Result};
 use crate::types::*;
 use crate::utils::{generate_uuid, parse_ai_response};
 
 /// Streaming chunk types
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum StreamChunk {
     /// Connection opened successfully
     ConnectionOpened,
@@ -27,12 +29,29 @@ pub enum StreamChunk {
     StatusUpdate(String),
     /// Raw data that couldn't be parsed
     RawData(String),
+    /// Keep-alive frame sent by the server while the answer is being generated
+    Heartbeat,
+    /// Final structured frame sent by the server before the stream ends
+    Completed {
+        finish_reason: Option<String>,
+        usage: Option<AnyObject>,
+        message_id: Option<String>,
+    },
     /// Stream completed successfully
     Done,
+    /// Connection closed before the answer was completed; always the last chunk
+    Incomplete { reason: String },
     /// Connection retry attempt
     Retry { attempt: u32, delay_ms: u64 },
 }
 
+/// Content delta paired with the full answer text received so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentDelta {
+    pub delta: String,
+    pub accumulated: String,
+}
+
 /// Configuration for streaming resilience
 #[derive(Debug, Clone)]
 pub struct StreamConfig {
@@ -42,10 +61,17 @@ pub struct StreamConfig {
     pub initial_retry_delay: u64,
     /// Maximum retry delay in milliseconds (for exponential backoff)
     pub max_retry_delay: u64,
-    /// Connection timeout in seconds
+    /// Time in seconds to wait for the stream to open. It does not limit
+    /// how long the answer then takes to stream.
     pub connection_timeout: u64,
-    /// Stream idle timeout in seconds
-    pub stream_timeout: u64,
+    /// Maximum total stream duration in seconds. Disabled when `None`, so
+    /// that only `idle_timeout` applies.
+    pub stream_timeout: Option<u64>,
+    /// Maximum time in seconds between two received frames, heartbeats
+    /// included. Disabled when `None`.
+    pub idle_timeout: Option<u64>,
+    /// Number of chunks buffered ahead of a slow consumer
+    pub channel_capacity: usize,
 }
 
 impl Default for StreamConfig {
@@ -55,7 +81,9 @@ impl Default for StreamConfig {
             initial_retry_delay: 1000, // 1 second
             max_retry_delay: 30000,    // 30 seconds
             connection_timeout: 30,    // 30 seconds
-            stream_timeout: 300,       // 5 minutes
+            stream_timeout: Some(300), // 5 minutes
+            idle_timeout: None,
+            channel_capacity: 32,
         }
     }
 }
@@ -65,6 +93,10 @@ impl Default for StreamConfig {
 pub struct CreateAiSessionConfig {
     pub llm_config: Option<LlmConfig>,
     pub initial_messages: Option<Vec<Message>>,
+    /// Record queries and answers in the session's message history
+    pub track_history: bool,
+    /// Token prices used by `estimated_cost`
+    pub pricing: Option<PricingTable>,
 }
 
 /// Answer configuration for AI requests
@@ -77,6 +109,8 @@ pub struct AnswerConfig {
     pub visitor_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
+    /// Conversation history sent with the query. When unset, the session's
+    /// history is used; when set, it replaces that history for this request.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub messages: Option<Vec<Message>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -91,6 +125,66 @@ pub struct AnswerConfig {
     pub ragat_notation: Option<String>,
     #[serde(rename = "LLMConfig", skip_serializing_if = "Option::is_none")]
     pub llm_config: Option<LlmConfig>,
+    /// System prompts to apply, resolved server-side, in order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_prompt_ids: Option<Vec<String>>,
+    /// Caller-supplied documents to answer from, bypassing retrieval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_documents: Option<Vec<serde_json::Value>>,
+    /// Overall deadline for the answer, including retrieval and generation
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+/// Step of the answer pipeline an interaction is in
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConversationStep {
+    Starting,
+    OptimizingQuery,
+    Searching,
+    GeneratingAnswer,
+    GeneratingRelatedQueries,
+    Completed,
+    /// A step this client does not know about
+    Other(String),
+}
+
+impl ConversationStep {
+    /// The step name as reported by the server
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Starting => "starting",
+            Self::OptimizingQuery => "optimizing_query",
+            Self::Searching => "searching",
+            Self::GeneratingAnswer => "generating_answer",
+            Self::GeneratingRelatedQueries => "generating_related_queries",
+            Self::Completed => "completed",
+            Self::Other(step) => step,
+        }
+    }
+}
+
+impl From<&str> for ConversationStep {
+    fn from(step: &str) -> Self {
+        match step {
+            "starting" => Self::Starting,
+            "optimizing_query" | "optimizingQuery" => Self::OptimizingQuery,
+            "search" | "searching" => Self::Searching,
+            "answer" | "generating_answer" => Self::GeneratingAnswer,
+            "related_queries" | "relatedQueries" | "generating_related_queries" => {
+                Self::GeneratingRelatedQueries
+            }
+            "completed" | "done" => Self::Completed,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ConversationStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Interaction state for conversations
@@ -105,11 +199,14 @@ pub struct Interaction {
     pub error_message: Option<String>,
     pub aborted: bool,
     pub related: Option<String>,
-    pub current_step: Option<String>,
+    pub current_step: Option<ConversationStep>,
     pub current_step_verbose: Option<String>,
     pub selected_llm: Option<LlmConfig>,
     pub optimized_query: Option<SearchParams>,
     pub advanced_autoquery: Option<serde_json::Value>,
+    pub finish_reason: Option<String>,
+    pub usage: Option<AnyObject>,
+    pub message_id: Option<String>,
 }
 
 impl Interaction {
@@ -125,26 +222,57 @@ impl Interaction {
             error_message: None,
             aborted: false,
             related: None,
-            current_step: Some("starting".to_string()),
+            current_step: Some(ConversationStep::Starting),
             current_step_verbose: None,
             selected_llm: None,
             optimized_query: None,
             advanced_autoquery: None,
+            finish_reason: None,
+            usage: None,
+            message_id: None,
         }
     }
+
+    /// The current step name, for display
+    pub fn current_step_str(&self) -> Option<&str> {
+        self.current_step.as_ref().map(ConversationStep::as_str)
+    }
+
+    /// The advanced autoquery plan, parsed from the raw `advanced_autoquery`
+    /// payload. `Ok(None)` when the server sent no plan.
+    pub fn advanced_autoquery_plan(&self) -> Result<Option<AdvancedAutoquery>> {
+        self.advanced_autoquery
+            .as_ref()
+            .filter(|value| !value.is_null())
+            .map(|value| {
+                serde_json::from_value(value.clone()).map_err(|e| {
+                    OramaError::deserialize::<AdvancedAutoquery, _>(
+                        &value.to_string(),
+                        e.to_string(),
+                    )
+                })
+            })
+            .transpose()
+    }
 }
 
 /// AI session stream manager
+///
+/// Safe to share between tasks behind an `Arc`; every setter takes `&self`.
 #[derive(Debug)]
 pub struct OramaCoreStream {
     collection_id: String,
     client: OramaClient,
     session_id: String,
-    llm_config: Option<LlmConfig>,
+    llm_config: RwLock<Option<LlmConfig>>,
     messages: Arc<RwLock<Vec<Message>>>,
     state: Arc<RwLock<Vec<Interaction>>>,
     last_interaction_params: Arc<RwLock<Option<AnswerConfig>>>,
-    stream_config: StreamConfig,
+    // Interior mutability so a session shared behind an `Arc` can be reconfigured
+    stream_config: std::sync::RwLock<StreamConfig>,
+    track_history: AtomicBool,
+    pricing: std::sync::RwLock<PricingTable>,
+    system_prompt_ids: Vec<String>,
 }
 
 impl OramaCoreStream {
@@ -154,11 +282,14 @@ impl OramaCoreStream {
             collection_id,
             client,
             session_id: generate_uuid(),
-            llm_config: None,
+            llm_config: RwLock::new(None),
             messages: Arc::new(RwLock::new(Vec::new())),
             state: Arc::new(RwLock::new(Vec::new())),
             last_interaction_params: Arc::new(RwLock::new(None)),
-            stream_config: StreamConfig::default(),
+            stream_config: std::sync::RwLock::new(StreamConfig::default()),
+            track_history: AtomicBool::new(true),
+            pricing: std::sync::RwLock::new(PricingTable::default()),
+            system_prompt_ids: Vec::new(),
         })
     }
 
@@ -174,11 +305,14 @@ impl OramaCoreStream {
             collection_id,
             client,
             session_id: generate_uuid(),
-            llm_config: config.llm_config,
+            llm_config: RwLock::new(config.llm_config),
             messages: Arc::new(RwLock::new(messages)),
             state: Arc::new(RwLock::new(Vec::new())),
             last_interaction_params: Arc::new(RwLock::new(None)),
-            stream_config: StreamConfig::default(),
+            stream_config: std::sync::RwLock::new(StreamConfig::default()),
+            track_history: AtomicBool::new(config.track_history),
+            pricing: std::sync::RwLock::new(config.pricing.unwrap_or_default()),
+            system_prompt_ids: Vec::new(),
         })
     }
 
@@ -195,17 +329,21 @@ impl OramaCoreStream {
             collection_id,
             client,
             session_id: generate_uuid(),
-            llm_config: config.llm_config,
+            llm_config: RwLock::new(config.llm_config),
             messages: Arc::new(RwLock::new(messages)),
             state: Arc::new(RwLock::new(Vec::new())),
             last_interaction_params: Arc::new(RwLock::new(None)),
-            stream_config,
+            stream_config: std::sync::RwLock::new(stream_config),
+            track_history: AtomicBool::new(config.track_history),
+            pricing: std::sync::RwLock::new(config.pricing.unwrap_or_default()),
+            system_prompt_ids: Vec::new(),
         })
     }
 
     /// Get a complete answer (non-streaming)
     pub async fn answer(&self, data: AnswerConfig) -> Result<String> {
         info!("Starting AI answer request");
+        data.validate()?;
         let enriched_config = self.enrich_config(data).await;
         debug!("Enriched config: {:?}", enriched_config);
 
@@ -216,7 +354,7 @@ impl OramaCoreStream {
         }
 
         // Add user message
-        {
+        if self.tracks_history() {
             let mut messages = self.messages.write().await;
             messages.push(Message {
                 role: Role::User,
@@ -234,22 +372,41 @@ impl OramaCoreStream {
             .clone()
             .unwrap_or_else(generate_uuid);
 
-        let interaction = Interaction::new(interaction_id.clone(), enriched_config.query.clone());
+        let mut interaction =
+            Interaction::new(interaction_id.clone(), enriched_config.query.clone());
+        interaction.selected_llm = enriched_config.llm_config.clone();
 
         {
             let mut state = self.state.write().await;
             state.push(interaction);
         }
 
+        let timeout = enriched_config.timeout;
+
         // Make the actual API call
         let request = ClientRequest::post(
             format!("/v1/collections/{}/ai/answer", self.collection_id),
             Target::Reader,
             ApiKeyPosition::QueryParams,
             enriched_config,
-        );
+        )
+        .with_json_repair(true);
+
+        let response_future = self.client.request::<_, serde_json::Value>(request);
+        let response_result = match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, response_future).await {
+                Ok(result) => result,
+                Err(_) => {
+                    let message = Self::deadline_message(timeout);
+                    error!("{}", message);
+                    Self::mark_interaction_error(self.state.clone(), message.clone()).await;
+                    return Err(OramaError::generic(message));
+                }
+            },
+            None => response_future.await,
+        };
 
-        let response: serde_json::Value = self.client.request(request).await.map_err(|e| {
+        let response = response_result.map_err(|e| {
             error!("API request failed: {}", e);
             e
         })?;
@@ -263,7 +420,7 @@ impl OramaCoreStream {
             if let Some(last_interaction) = state.last_mut() {
                 last_interaction.response = answer.clone();
                 last_interaction.loading = false;
-                last_interaction.current_step = Some("completed".to_string());
+                last_interaction.current_step = Some(ConversationStep::Completed);
 
                 // Update with additional response data if available
                 if let Some(sources) = response.get("sources") {
@@ -272,10 +429,18 @@ impl OramaCoreStream {
                 if let Some(_related) = response.get("related") {
                     last_interaction.related = response["related"].as_str().map(String::from);
                 }
+                if let Some(plan) = response.get("advanced_autoquery").filter(|v| !v.is_null()) {
+                    last_interaction.advanced_autoquery = Some(plan.clone());
+                }
+                // Same metadata as the terminal frame of a streamed answer
+                last_interaction.usage = response.get("usage").filter(|v| !v.is_null()).cloned();
+                last_interaction.finish_reason =
+                    response["finish_reason"].as_str().map(String::from);
+                last_interaction.message_id = response["message_id"].as_str().map(String::from);
             }
         }
 
-        {
+        if self.tracks_history() {
             let mut messages = self.messages.write().await;
             if let Some(last_message) = messages.last_mut() {
                 last_message.content = answer.clone();
@@ -286,95 +451,177 @@ impl OramaCoreStream {
         Ok(answer)
     }
 
+    /// Build the streaming answer request.
+    ///
+    /// It is sent through [`OramaClient`], so the reader credentials are
+    /// resolved, cached and refreshed exactly like for any other request.
+    fn stream_request<'a>(
+        &self,
+        enriched_config: &'a AnswerConfig,
+    ) -> ClientRequest<&'a AnswerConfig> {
+        ClientRequest::post(
+            format!("/v1/collections/{}/ai/answer/stream", self.collection_id),
+            Target::Reader,
+            ApiKeyPosition::Header,
+            enriched_config,
+        )
+        .with_header("Connection", "keep-alive")
+    }
+
     /// Create resilient SSE stream with retry logic
     async fn create_resilient_stream(
         &self,
-        client: OramaClient,
-        stream_url: String,
-        auth_ref: crate::auth::AuthRef,
         enriched_config: AnswerConfig,
-        messages: Arc<RwLock<Vec<Message>>>,
+        messages: Option<Arc<RwLock<Vec<Message>>>>,
         state: Arc<RwLock<Vec<Interaction>>>,
     ) -> Result<impl Stream<Item = Result<StreamChunk>> + Send> {
-        let stream_timeout = Duration::from_secs(self.stream_config.stream_timeout);
-        let start_time = std::time::Instant::now();
-
-        // Create request builder for EventSource
-        let request_builder = client
-            .inner()
-            .post(&stream_url)
-            .header("Accept", "text/event-stream")
-            .header("Cache-Control", "no-cache")
-            .header("Connection", "keep-alive")
-            .header("Authorization", format!("Bearer {}", auth_ref.bearer))
-            .timeout(Duration::from_secs(self.stream_config.connection_timeout))
-            .json(&enriched_config);
-
-        // Create EventSource
-        let event_source = EventSource::new(request_builder).map_err(|e| {
-            error!("Failed to create EventSource: {}", e);
-            OramaError::generic(format!("EventSource creation failed: {e}"))
-        })?;
-
-        info!("Successfully created EventSource for streaming");
-
-        // Convert EventSource to stream with comprehensive error handling
-        let event_stream = event_source.map(move |event_result| {
-            // Check for timeout
-            if start_time.elapsed() >= stream_timeout {
-                let timeout_secs = stream_timeout.as_secs();
-                error!("Stream timeout after {} seconds", timeout_secs);
-                let state_clone = state.clone();
-                let timeout_msg = format!("Stream timeout after {timeout_secs} seconds");
-                tokio::spawn(async move {
-                    Self::mark_interaction_error(state_clone, timeout_msg).await;
-                });
-                return Err(OramaError::generic(format!(
-                    "Stream timeout after {timeout_secs} seconds"
-                )));
+        let stream_config = self.get_stream_config();
+        let stream_timeout = stream_config.stream_timeout.map(Duration::from_secs);
+        let idle_timeout = stream_config.idle_timeout.map(Duration::from_secs);
+        let stream_deadline = stream_timeout.map(|timeout| tokio::time::Instant::now() + timeout);
+
+        let request = self.stream_request(&enriched_config);
+        let stream_path = request.path.clone();
+        // Only opening the stream is bounded here; a per-request timeout
+        // would also cut off the body once it is flowing
+        let connection_timeout = Duration::from_secs(stream_config.connection_timeout);
+        let event_source =
+            tokio::time::timeout(connection_timeout, self.client.event_stream(&request))
+                .await
+                .map_err(|_| {
+                    OramaError::stream(format!(
+                        "Stream connection timeout after {} seconds",
+                        connection_timeout.as_secs()
+                    ))
+                })??;
+        info!("Opened streaming answer connection");
+
+        // Race every read against the total and idle deadlines, so a silent
+        // connection still times out. The idle deadline restarts with each
+        // frame; the stream ends right after a timeout is reported.
+        let events = futures::stream::unfold(Some(event_source), move |event_source| async move {
+            let mut event_source = event_source?;
+
+            let stream_cap = stream_deadline.zip(stream_timeout).map(|(at, timeout)| {
+                let message = format!("Stream timeout after {} seconds", timeout.as_secs());
+                (at, message)
+            });
+            let idle_cap = idle_timeout.map(|idle| {
+                let message = format!(
+                    "Stream idle timeout: no data received for {} seconds",
+                    idle.as_secs()
+                );
+                (tokio::time::Instant::now() + idle, message)
+            });
+            let deadline = [stream_cap, idle_cap]
+                .into_iter()
+                .flatten()
+                .min_by_key(|(at, _)| *at);
+
+            let Some((at, timeout_msg)) = deadline else {
+                let event = event_source.next().await?;
+                return Some((Ok(event), Some(event_source)));
+            };
+
+            tokio::select! {
+                event = event_source.next() => Some((Ok(event?), Some(event_source))),
+                _ = tokio::time::sleep_until(at) => Some((Err(timeout_msg), None)),
             }
+        });
 
-            match event_result {
-                Ok(event) => match event {
-                    Event::Open => {
-                        debug!("Stream connection opened");
-                        Ok(StreamChunk::ConnectionOpened)
-                    }
-                    Event::Message(message) => {
-                        debug!("Received streaming message: {}", message.data);
-
-                        match message.data.as_str() {
-                            "[DONE]" => {
-                                info!("Streaming completed successfully");
-                                let state_clone = state.clone();
-                                tokio::spawn(async move {
-                                    let mut state = state_clone.write().await;
+        // Convert EventSource to stream with comprehensive error handling.
+        // State updates are awaited inline rather than spawned, so nothing
+        // touches the session once the stream is dropped.
+        let event_stream = events
+            .then(move |event_result| {
+                let messages = messages.clone();
+                let state = state.clone();
+                let stream_path = stream_path.clone();
+
+                async move {
+                    let event_result = match event_result {
+                        Ok(event_result) => event_result,
+                        Err(timeout_msg) => {
+                            error!("{}", timeout_msg);
+                            Self::mark_interaction_error(state, timeout_msg.clone()).await;
+                            return Some(Err(OramaError::generic(timeout_msg)));
+                        }
+                    };
+
+                    let chunk = match event_result {
+                        Ok(Event::Open) => {
+                            debug!("Stream connection opened");
+                            Ok(StreamChunk::ConnectionOpened)
+                        }
+                        Ok(Event::Message(message)) => {
+                            debug!("Received streaming message: {}", message.data);
+
+                            if Self::is_heartbeat(&message.event, &message.data) {
+                                return Some(Ok(StreamChunk::Heartbeat));
+                            }
+
+                            match message.data.as_str() {
+                                "[DONE]" => {
+                                    info!("Streaming completed successfully");
+                                    let mut state = state.write().await;
                                     if let Some(interaction) = state.last_mut() {
                                         interaction.loading = false;
-                                        interaction.current_step = Some("completed".to_string());
+                                        interaction.current_step =
+                                            Some(ConversationStep::Completed);
                                     }
-                                });
-                                Ok(StreamChunk::Done)
+                                    Ok(StreamChunk::Done)
+                                }
+                                data => Self::process_stream_data(data, messages, state).await,
                             }
-                            data => {
-                                Self::process_stream_data(data, messages.clone(), state.clone())
+                        }
+                        Err(reqwest_eventsource::Error::StreamEnded) => {
+                            let still_loading = state
+                                .read()
+                                .await
+                                .last()
+                                .is_some_and(|interaction| interaction.loading);
+                            if !still_loading {
+                                debug!("Stream connection closed");
+                                return None;
                             }
+
+                            let reason = "Stream ended before the answer was completed".to_string();
+                            warn!("{}", reason);
+                            Self::mark_interaction_error(state, reason.clone()).await;
+                            Ok(StreamChunk::Incomplete { reason })
                         }
-                    }
-                },
-                Err(event_error) => {
-                    error!("Stream event error: {}", event_error);
-                    let state_clone = state.clone();
-                    let error_msg = event_error.to_string();
-                    tokio::spawn(async move {
-                        Self::mark_interaction_error(state_clone, error_msg).await;
-                    });
-                    Err(OramaError::generic(format!(
-                        "Stream event error: {event_error}"
-                    )))
+                        Err(reqwest_eventsource::Error::InvalidStatusCode(status)) => {
+                            let error = OramaError::from_response(
+                                status.as_u16(),
+                                status.canonical_reason().unwrap_or_default(),
+                                Some(RequestContext::new("POST", stream_path)),
+                            );
+                            error!("Stream request failed: {}", error);
+                            Self::mark_interaction_error(state, error.to_string()).await;
+                            Err(error)
+                        }
+                        Err(event_error) => {
+                            error!("Stream event error: {}", event_error);
+                            Self::mark_interaction_error(state, event_error.to_string()).await;
+                            Err(OramaError::generic(format!(
+                                "Stream event error: {event_error}"
+                            )))
+                        }
+                    };
+                    Some(chunk)
                 }
-            }
-        });
+            })
+            // Stop at a clean close, and right after reporting an abnormal one
+            // or an error, instead of reading from a failed connection
+            .scan(false, |finished, chunk| {
+                if *finished {
+                    return future::ready(None);
+                }
+                if matches!(chunk, Some(Ok(StreamChunk::Incomplete { .. }) | Err(_))) {
+                    *finished = true;
+                }
+                future::ready(chunk)
+            });
 
         Ok(event_stream)
     }
@@ -385,6 +632,7 @@ impl OramaCoreStream {
         data: AnswerConfig,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk>> + Send>>> {
         info!("Starting streaming AI answer request");
+        data.validate()?;
         let enriched_config = self.enrich_config(data).await;
         debug!("Enriched streaming config: {:?}", enriched_config);
 
@@ -395,7 +643,7 @@ impl OramaCoreStream {
         }
 
         // Add user message
-        {
+        if self.tracks_history() {
             let mut messages = self.messages.write().await;
             messages.push(Message {
                 role: Role::User,
@@ -413,44 +661,143 @@ impl OramaCoreStream {
             .clone()
             .unwrap_or_else(generate_uuid);
 
-        let interaction = Interaction::new(interaction_id.clone(), enriched_config.query.clone());
+        let mut interaction =
+            Interaction::new(interaction_id.clone(), enriched_config.query.clone());
+        interaction.selected_llm = enriched_config.llm_config.clone();
 
         {
             let mut state = self.state.write().await;
             state.push(interaction);
         }
 
-        let client = self.client.clone();
-        let collection_id = self.collection_id.clone();
-        let messages = self.messages.clone();
+        let messages = self.tracks_history().then(|| self.messages.clone());
         let state = self.state.clone();
+        let deadline = enriched_config
+            .timeout
+            .map(|timeout| (timeout, tokio::time::Instant::now() + timeout));
 
-        // Get auth reference for the streaming request
-        let auth_ref = client.get_auth_ref(Target::Reader).await.map_err(|e| {
-            error!("Failed to get auth reference: {}", e);
-            e
-        })?;
+        // Create SSE stream using reqwest-eventsource with retry
+        let stream = self
+            .create_resilient_stream(enriched_config, messages.clone(), state.clone())
+            .await?;
+
+        let capacity = self.get_stream_config().channel_capacity;
+        let Some((timeout, deadline)) = deadline else {
+            return Ok(Self::buffered(stream, capacity));
+        };
 
-        let base_url = &auth_ref.base_url;
-        let stream_url = format!("{base_url}/v1/collections/{collection_id}/ai/answer/stream");
+        // Cut the stream off at the deadline and surface it as an error
+        let deadline_error = futures::stream::once(async move {
+            if tokio::time::Instant::now() < deadline {
+                return None;
+            }
 
-        debug!("Creating streaming request to: {}", stream_url);
+            let message = Self::deadline_message(timeout);
+            error!("{}", message);
+            Self::mark_interaction_error(state, message.clone()).await;
+            Some(Err(OramaError::generic(message)))
+        })
+        .filter_map(futures::future::ready);
 
-        // Create SSE stream using reqwest-eventsource with retry
-        let stream = self
-            .create_resilient_stream(
-                client.clone(),
-                stream_url,
-                auth_ref,
-                enriched_config,
-                messages.clone(),
-                state.clone(),
-            )
+        let stream = stream
+            .take_until(tokio::time::sleep_until(deadline))
+            .chain(deadline_error);
+
+        Ok(Self::buffered(stream, capacity))
+    }
+
+    /// Drive a stream from a background task into a bounded channel, so the
+    /// producer can read at most `capacity` chunks ahead of the consumer.
+    ///
+    /// The task stops as soon as the returned stream is dropped.
+    fn buffered<S>(
+        stream: S,
+        capacity: usize,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamChunk>> + Send>>
+    where
+        S: Stream<Item = Result<StreamChunk>> + Send + 'static,
+    {
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity.max(1));
+
+        tokio::spawn(async move {
+            let mut stream = std::pin::pin!(stream);
+            loop {
+                let item = tokio::select! {
+                    item = stream.next() => item,
+                    _ = sender.closed() => break,
+                };
+                let Some(item) = item else { break };
+                // Reading past `Done` would only hit the closed connection
+                let done = matches!(item, Ok(StreamChunk::Done));
+                if sender.send(item).await.is_err() || done {
+                    break;
+                }
+            }
+        });
+
+        Box::pin(tokio_stream::wrappers::ReceiverStream::new(receiver))
+    }
+
+    /// Get a streaming answer as the raw SSE `data` payloads, verbatim.
+    ///
+    /// Frames are neither parsed nor recorded in the session, which makes this
+    /// suitable for proxying the stream to another client unchanged.
+    pub async fn answer_stream_raw(
+        &self,
+        data: AnswerConfig,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<String>> + Send>>> {
+        data.validate()?;
+        let enriched_config = self.enrich_config(data).await;
+        let event_source = self
+            .client
+            .event_stream(&self.stream_request(&enriched_config))
             .await?;
 
+        let stream = event_source
+            .take_while(|event_result| {
+                let ended = matches!(event_result, Err(reqwest_eventsource::Error::StreamEnded));
+                futures::future::ready(!ended)
+            })
+            .filter_map(|event_result| {
+                let item = match event_result {
+                    Ok(Event::Open) => None,
+                    Ok(Event::Message(message)) => Some(Ok(message.data)),
+                    Err(e) => Some(Err(OramaError::stream(format!("Stream event error: {e}")))),
+                };
+                futures::future::ready(item)
+            });
+
         Ok(Box::pin(stream))
     }
 
+    /// Get a streaming answer that yields each content delta together with
+    /// the text accumulated so far
+    pub async fn answer_stream_accumulated(
+        &self,
+        data: AnswerConfig,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ContentDelta>> + Send>>> {
+        let stream = self.answer_stream(data).await?;
+
+        let accumulated = stream
+            .scan(String::new(), |accumulated, chunk_result| {
+                let item = match chunk_result {
+                    Ok(StreamChunk::Content(delta)) => {
+                        accumulated.push_str(&delta);
+                        Some(Ok(ContentDelta {
+                            delta,
+                            accumulated: accumulated.clone(),
+                        }))
+                    }
+                    Ok(_) => None,
+                    Err(e) => Some(Err(e)),
+                };
+                futures::future::ready(Some(item))
+            })
+            .filter_map(futures::future::ready);
+
+        Ok(Box::pin(accumulated))
+    }
+
     /// Regenerate the last response
     pub async fn regenerate_last(&self, stream: bool) -> Result<String> {
         info!("Starting regenerate_last, stream: {}", stream);
@@ -505,10 +852,62 @@ impl OramaCoreStream {
             state.pop();
         }
 
+        self.run_regeneration(last_params, stream).await
+    }
+
+    /// Replace the last query and regenerate its answer.
+    ///
+    /// The last user/assistant message pair and interaction are replaced; all
+    /// other parameters of the last request (LLM config, filters, ...) are kept.
+    pub async fn edit_last_and_regenerate(
+        &self,
+        new_query: String,
+        stream: bool,
+    ) -> Result<String> {
+        info!("Starting edit_last_and_regenerate, stream: {}", stream);
+
+        let last_params = {
+            let params = self.last_interaction_params.read().await;
+            params.clone()
+        };
+
+        let mut last_params = last_params.ok_or_else(|| {
+            warn!("No last interaction parameters available");
+            OramaError::generic("No last interaction parameters available")
+        })?;
+
+        // Remove the last user/assistant pair and state
+        if self.tracks_history() {
+            let mut messages = self.messages.write().await;
+            let len = messages.len();
+            let is_pair = len >= 2
+                && matches!(messages[len - 2].role, Role::User)
+                && matches!(messages[len - 1].role, Role::Assistant);
+
+            if !is_pair {
+                warn!("Last messages are not a user/assistant pair");
+                return Err(OramaError::generic(
+                    "Last messages are not a user/assistant pair",
+                ));
+            }
+            messages.truncate(len - 2);
+        }
+
+        {
+            let mut state = self.state.write().await;
+            state.pop();
+        }
+
+        last_params.query = new_query;
+        self.run_regeneration(last_params, stream).await
+    }
+
+    /// Answer again with the given parameters, collecting streamed output
+    async fn run_regeneration(&self, params: AnswerConfig, stream: bool) -> Result<String> {
         // Regenerate based on stream preference
         if stream {
             info!("Regenerating with streaming");
-            let mut stream_result = self.answer_stream(last_params).await?;
+            let mut stream_result = self.answer_stream(params).await?;
             let mut complete_response = String::new();
 
             // Collect the stream
@@ -520,6 +919,9 @@ impl OramaCoreStream {
                     StreamChunk::Done => {
                         break;
                     }
+                    StreamChunk::Incomplete { reason } => {
+                        return Err(OramaError::stream(reason));
+                    }
                     StreamChunk::StatusUpdate(status) => {
                         debug!("Status update during regeneration: {}", status);
                     }
@@ -532,7 +934,7 @@ impl OramaCoreStream {
             Ok(complete_response)
         } else {
             info!("Regenerating without streaming");
-            self.answer(last_params).await
+            self.answer(params).await
         }
     }
 
@@ -549,6 +951,24 @@ impl OramaCoreStream {
         }
     }
 
+    /// Clear the conversation but keep the leading system messages, e.g. to
+    /// start a new chat with the same assistant persona
+    pub async fn clear_session_keep_system(&self) {
+        {
+            let mut messages = self.messages.write().await;
+            let system_len = messages
+                .iter()
+                .take_while(|message| matches!(message.role, Role::System))
+                .count();
+            messages.truncate(system_len);
+        }
+
+        {
+            let mut state = self.state.write().await;
+            state.clear();
+        }
+    }
+
     /// Get current messages
     pub async fn get_messages(&self) -> Vec<Message> {
         let messages = self.messages.read().await;
@@ -561,19 +981,112 @@ impl OramaCoreStream {
         state.clone()
     }
 
+    /// Get the most recent interaction
+    pub async fn last_interaction(&self) -> Option<Interaction> {
+        let state = self.state.read().await;
+        state.last().cloned()
+    }
+
+    /// Get an interaction by its ID
+    pub async fn interaction_by_id(&self, id: &str) -> Option<Interaction> {
+        let state = self.state.read().await;
+        state
+            .iter()
+            .find(|interaction| interaction.id == id)
+            .cloned()
+    }
+
+    /// Number of interactions in the session
+    pub async fn interaction_count(&self) -> usize {
+        let state = self.state.read().await;
+        state.len()
+    }
+
     /// Get session ID
     pub fn session_id(&self) -> &str {
         &self.session_id
     }
 
     /// Get current stream configuration
-    pub fn get_stream_config(&self) -> &StreamConfig {
-        &self.stream_config
+    pub fn get_stream_config(&self) -> StreamConfig {
+        self.stream_config.read().unwrap().clone()
+    }
+
+    /// Replace the session's default LLM config, e.g. to escalate to a larger
+    /// model mid-session. Applies to subsequent answers.
+    pub async fn set_llm_config(&self, config: LlmConfig) {
+        *self.llm_config.write().await = Some(config);
     }
 
-    /// Update stream configuration
-    pub fn set_stream_config(&mut self, config: StreamConfig) {
-        self.stream_config = config;
+    /// Get the session's default LLM config
+    pub async fn llm_config(&self) -> Option<LlmConfig> {
+        self.llm_config.read().await.clone()
+    }
+
+    /// Enable or disable recording queries and answers in the session's
+    /// message history. When disabled, `regenerate_last` is unavailable.
+    pub fn set_track_history(&self, track_history: bool) {
+        self.track_history.store(track_history, Ordering::Relaxed);
+    }
+
+    /// Whether queries and answers are recorded in the message history
+    pub fn tracks_history(&self) -> bool {
+        self.track_history.load(Ordering::Relaxed)
+    }
+
+    /// Attach a system prompt from the collection to every answer in this
+    /// session. Prompts apply in the order they are added.
+    pub fn with_system_prompt_id<S: Into<String>>(mut self, id: S) -> Self {
+        self.system_prompt_ids.push(id.into());
+        self
+    }
+
+    /// Replace the token prices used by `estimated_cost`
+    pub fn set_pricing(&self, pricing: PricingTable) {
+        *self.pricing.write().unwrap() = pricing;
+    }
+
+    /// Estimate the cost of the session from the token usage reported by
+    /// the server, for both streamed and non-streamed answers.
+    ///
+    /// Returns `None` when no usage was reported or a used model has no price.
+    pub async fn estimated_cost(&self) -> Option<Cost> {
+        let default_llm = self.llm_config.read().await.clone();
+        let state = self.state.read().await;
+        let pricing_table = self.pricing.read().unwrap().clone();
+
+        let mut cost = Cost::default();
+        let mut has_usage = false;
+
+        for interaction in state.iter() {
+            let Some(usage) = &interaction.usage else {
+                continue;
+            };
+            let tokens = |keys: [&str; 2]| {
+                keys.iter()
+                    .find_map(|key| usage.get(*key).and_then(|v| v.as_u64()))
+                    .unwrap_or(0)
+            };
+            let input_tokens = tokens(["prompt_tokens", "input_tokens"]);
+            let output_tokens = tokens(["completion_tokens", "output_tokens"]);
+
+            let llm = interaction.selected_llm.as_ref().or(default_llm.as_ref())?;
+            let pricing = pricing_table.get(&llm.provider, &llm.model)?;
+
+            has_usage = true;
+            cost.input_tokens += input_tokens;
+            cost.output_tokens += output_tokens;
+            cost.amount += (input_tokens as f64 * pricing.input_per_million
+                + output_tokens as f64 * pricing.output_per_million)
+                / 1_000_000.0;
+        }
+
+        has_usage.then_some(cost)
+    }
+
+    /// Update stream configuration; applies to streams opened afterwards
+    pub fn set_stream_config(&self, config: StreamConfig) {
+        *self.stream_config.write().unwrap() = config;
     }
 
     /// Enrich config with default values
@@ -590,87 +1103,124 @@ impl OramaCoreStream {
             config.session_id = Some(self.session_id.clone());
         }
 
+        // Explicit history overrides the session's; otherwise send the session's
+        if config.messages.is_none() {
+            let messages = self.messages.read().await;
+            if !messages.is_empty() {
+                config.messages = Some(messages.clone());
+            }
+        }
+
+        // Per-request system prompts replace the session's
+        if config.system_prompt_ids.is_none() && !self.system_prompt_ids.is_empty() {
+            config.system_prompt_ids = Some(self.system_prompt_ids.clone());
+        }
+
         // Use session's LLM config if none is provided in the request
         if config.llm_config.is_none() {
-            config.llm_config = self.llm_config.clone();
+            config.llm_config = self.llm_config.read().await.clone();
+        }
+
+        // Retrieval tuning is meaningless when the caller supplies the context
+        if config.context_documents.is_some() {
+            config.min_similarity = None;
+            config.max_documents = None;
         }
 
         config
     }
 
-    /// Process streaming data chunk with robust JSON parsing
-    fn process_stream_data(
+    /// Process streaming data chunk with robust JSON parsing, applying any
+    /// state updates before the chunk is yielded
+    async fn process_stream_data(
         data: &str,
-        messages: Arc<RwLock<Vec<Message>>>,
+        messages: Option<Arc<RwLock<Vec<Message>>>>,
         state: Arc<RwLock<Vec<Interaction>>>,
     ) -> Result<StreamChunk> {
         // Use robust AI response parsing with automatic JSON fixing
         match parse_ai_response::<serde_json::Value>(data) {
             Ok(parsed) => {
+                // The query plan can ride along on any frame
+                if let Some(plan) = parsed.get("advanced_autoquery").filter(|v| !v.is_null()) {
+                    let mut state = state.write().await;
+                    if let Some(last_interaction) = state.last_mut() {
+                        last_interaction.advanced_autoquery = Some(plan.clone());
+                    }
+                }
+
                 if let Some(content) = parsed.get("content").and_then(|c| c.as_str()) {
                     // Content chunk - update message and interaction
-                    let content = content.to_string();
-                    let content_for_update = content.clone();
-                    let parsed_clone = parsed.clone();
-
-                    tokio::spawn(async move {
-                        // Update assistant message
-                        {
-                            let mut messages = messages.write().await;
-                            if let Some(last_message) = messages.last_mut() {
-                                if matches!(last_message.role, Role::Assistant) {
-                                    last_message.content.push_str(&content_for_update);
-                                }
+                    if let Some(messages) = messages {
+                        let mut messages = messages.write().await;
+                        if let Some(last_message) = messages.last_mut() {
+                            if matches!(last_message.role, Role::Assistant) {
+                                last_message.content.push_str(content);
                             }
                         }
+                    }
 
-                        // Update interaction state
-                        {
-                            let mut state = state.write().await;
-                            if let Some(last_interaction) = state.last_mut() {
-                                last_interaction.response.push_str(&content_for_update);
-
-                                // Update step if provided
-                                if let Some(step) =
-                                    parsed_clone.get("step").and_then(|s| s.as_str())
-                                {
-                                    last_interaction.current_step = Some(step.to_string());
-                                }
+                    {
+                        let mut state = state.write().await;
+                        if let Some(last_interaction) = state.last_mut() {
+                            last_interaction.response.push_str(content);
 
-                                // Update verbose step if provided
-                                if let Some(verbose) =
-                                    parsed_clone.get("verbose_step").and_then(|s| s.as_str())
-                                {
-                                    last_interaction.current_step_verbose =
-                                        Some(verbose.to_string());
-                                }
+                            // Update step if provided
+                            if let Some(step) = parsed.get("step").and_then(|s| s.as_str()) {
+                                last_interaction.current_step = Some(ConversationStep::from(step));
+                            }
+
+                            // Update verbose step if provided
+                            if let Some(verbose) =
+                                parsed.get("verbose_step").and_then(|s| s.as_str())
+                            {
+                                last_interaction.current_step_verbose = Some(verbose.to_string());
                             }
                         }
-                    });
+                    }
 
-                    Ok(StreamChunk::Content(content))
+                    Ok(StreamChunk::Content(content.to_string()))
+                } else if Self::is_terminal_frame(&parsed) {
+                    // Final structured frame (finish reason, usage, message id)
+                    let finish_reason = parsed
+                        .get("finish_reason")
+                        .and_then(|f| f.as_str())
+                        .map(String::from);
+                    let usage = parsed.get("usage").cloned();
+                    let message_id = parsed
+                        .get("message_id")
+                        .and_then(|m| m.as_str())
+                        .map(String::from);
+
+                    {
+                        let mut state = state.write().await;
+                        if let Some(last_interaction) = state.last_mut() {
+                            last_interaction.loading = false;
+                            last_interaction.current_step = Some(ConversationStep::Completed);
+                            last_interaction.finish_reason = finish_reason.clone();
+                            last_interaction.usage = usage.clone();
+                            last_interaction.message_id = message_id.clone();
+                        }
+                    }
+
+                    Ok(StreamChunk::Completed {
+                        finish_reason,
+                        usage,
+                        message_id,
+                    })
                 } else if let Some(step) = parsed.get("step").and_then(|s| s.as_str()) {
                     // Status update
-                    let step = step.to_string();
-                    let step_for_update = step.clone();
-
-                    tokio::spawn(async move {
+                    {
                         let mut state = state.write().await;
                         if let Some(last_interaction) = state.last_mut() {
-                            last_interaction.current_step = Some(step_for_update);
+                            last_interaction.current_step = Some(ConversationStep::from(step));
                         }
-                    });
+                    }
 
-                    Ok(StreamChunk::StatusUpdate(step))
+                    Ok(StreamChunk::StatusUpdate(step.to_string()))
                 } else if let Some(error_msg) = parsed.get("error").and_then(|e| e.as_str()) {
                     // Error in stream
                     warn!("Stream error received: {}", error_msg);
-
-                    let state_clone = state.clone();
-                    let error_message = error_msg.to_string();
-                    tokio::spawn(async move {
-                        Self::mark_interaction_error(state_clone, error_message).await;
-                    });
+                    Self::mark_interaction_error(state, error_msg.to_string()).await;
                     Err(OramaError::generic(error_msg))
                 } else {
                     // Unknown structured data
@@ -689,6 +1239,29 @@ impl OramaCoreStream {
         }
     }
 
+    /// Whether an SSE message is a keep-alive frame rather than answer data
+    fn is_heartbeat(event: &str, data: &str) -> bool {
+        let is_keep_alive = |s: &str| ["ping", "heartbeat", "keep-alive", "keepalive"].contains(&s);
+        let data = data.trim();
+
+        data.is_empty()
+            || data.starts_with(':')
+            || is_keep_alive(event)
+            || is_keep_alive(&data.to_ascii_lowercase())
+    }
+
+    /// Error message used when an answer exceeds its deadline
+    fn deadline_message(timeout: Duration) -> String {
+        format!("Answer deadline of {}ms exceeded", timeout.as_millis())
+    }
+
+    /// Whether a parsed frame is the terminal structured payload
+    fn is_terminal_frame(parsed: &serde_json::Value) -> bool {
+        ["finish_reason", "usage", "message_id"]
+            .iter()
+            .any(|key| parsed.get(key).is_some_and(|v| !v.is_null()))
+    }
+
     /// Mark interaction as errored (async version)
     async fn mark_interaction_error(state: Arc<RwLock<Vec<Interaction>>>, error_message: String) {
         let mut state = state.write().await;
@@ -716,7 +1289,38 @@ impl AnswerConfig {
             max_documents: None,
             ragat_notation: None,
             llm_config: None,
+            system_prompt_ids: None,
+            context_documents: None,
+            timeout: None,
+        }
+    }
+
+    /// Check values that would otherwise silently leave the answer without
+    /// context: `min_similarity` must be within `0.0..=1.0` and
+    /// `max_documents` at least 1.
+    ///
+    /// ```
+    /// use oramacore_client::stream_manager::AnswerConfig;
+    ///
+    /// assert!(AnswerConfig::new("q").with_min_similarity(1.0).validate().is_ok());
+    /// assert!(AnswerConfig::new("q").with_min_similarity(1.01).validate().is_err());
+    /// assert!(AnswerConfig::new("q").with_max_documents(1).validate().is_ok());
+    /// assert!(AnswerConfig::new("q").with_max_documents(0).validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        if let Some(similarity) = self.min_similarity {
+            if !(0.0..=1.0).contains(&similarity) {
+                return Err(OramaError::config(format!(
+                    "min_similarity must be between 0.0 and 1.0, got {similarity}"
+                )));
+            }
+        }
+
+        if self.max_documents == Some(0) {
+            return Err(OramaError::config("max_documents must be at least 1"));
         }
+
+        Ok(())
     }
 
     /// Set interaction ID
@@ -737,7 +1341,8 @@ impl AnswerConfig {
         self
     }
 
-    /// Set messages
+    /// Set the conversation history, replacing the session's history for
+    /// this request
     pub fn with_messages(mut self, messages: Vec<Message>) -> Self {
         self.messages = Some(messages);
         self
@@ -778,6 +1383,29 @@ impl AnswerConfig {
         self.llm_config = Some(config);
         self
     }
+
+    /// Set an overall deadline for the answer
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Add a system prompt from the collection, replacing the session's
+    /// prompts for this request. Prompts apply in the order they are added.
+    pub fn with_system_prompt_id<S: Into<String>>(mut self, id: S) -> Self {
+        self.system_prompt_ids
+            .get_or_insert_with(Vec::new)
+            .push(id.into());
+        self
+    }
+
+    /// Set the documents to answer from, skipping the retrieval step.
+    ///
+    /// When set, `min_similarity` and `max_documents` are ignored.
+    pub fn with_context_documents(mut self, documents: Vec<serde_json::Value>) -> Self {
+        self.context_documents = Some(documents);
+        self
+    }
 }
 
 impl CreateAiSessionConfig {
@@ -786,6 +1414,8 @@ impl CreateAiSessionConfig {
         Self {
             llm_config: None,
             initial_messages: None,
+            track_history: true,
+            pricing: None,
         }
     }
 
@@ -800,6 +1430,18 @@ impl CreateAiSessionConfig {
         self.initial_messages = Some(messages);
         self
     }
+
+    /// Enable or disable recording queries and answers in the message history
+    pub fn with_track_history(mut self, track_history: bool) -> Self {
+        self.track_history = track_history;
+        self
+    }
+
+    /// Set the token prices used to estimate the session's cost
+    pub fn with_pricing(mut self, pricing: PricingTable) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
 }
 
 impl Default for CreateAiSessionConfig {
@@ -807,3 +1449,182 @@ impl Default for CreateAiSessionConfig {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{ApiKeyAuth, Auth, AuthConfig};
+
+    async fn session() -> OramaCoreStream {
+        let auth = Auth::new(
+            AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url("http://localhost")),
+            Arc::new(reqwest::Client::new()),
+        );
+        OramaCoreStream::new("col".to_string(), OramaClient::new(auth).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn context_documents_are_serialized() {
+        let config = AnswerConfig::new("q")
+            .with_context_documents(vec![serde_json::json!({ "id": "1", "text": "hello" })]);
+
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::json!({
+                "query": "q",
+                "context_documents": [{ "id": "1", "text": "hello" }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn context_documents_drop_retrieval_tuning() {
+        let config = AnswerConfig::new("q")
+            .with_min_similarity(0.8)
+            .with_max_documents(3)
+            .with_context_documents(vec![serde_json::json!({ "id": "1" })]);
+
+        let enriched = session().await.enrich_config(config).await;
+
+        assert_eq!(enriched.min_similarity, None);
+        assert_eq!(enriched.max_documents, None);
+        assert!(enriched.context_documents.is_some());
+    }
+
+    #[tokio::test]
+    async fn interactions_are_looked_up_by_position_and_id() {
+        let session = session().await;
+        assert!(session.last_interaction().await.is_none());
+
+        session.state.write().await.extend([
+            Interaction::new("first".to_string(), "q1".to_string()),
+            Interaction::new("second".to_string(), "q2".to_string()),
+        ]);
+
+        assert_eq!(session.interaction_count().await, 2);
+        assert_eq!(session.last_interaction().await.unwrap().id, "second");
+        assert_eq!(
+            session.interaction_by_id("first").await.unwrap().query,
+            "q1"
+        );
+        assert!(session.interaction_by_id("missing").await.is_none());
+    }
+
+    #[test]
+    fn known_steps_parse_into_variants() {
+        for (name, step) in [
+            ("starting", ConversationStep::Starting),
+            ("optimizing_query", ConversationStep::OptimizingQuery),
+            ("optimizingQuery", ConversationStep::OptimizingQuery),
+            ("search", ConversationStep::Searching),
+            ("answer", ConversationStep::GeneratingAnswer),
+            ("relatedQueries", ConversationStep::GeneratingRelatedQueries),
+            ("done", ConversationStep::Completed),
+        ] {
+            assert_eq!(ConversationStep::from(name), step, "{name}");
+        }
+        assert_eq!(ConversationStep::from("search").as_str(), "searching");
+    }
+
+    #[test]
+    fn unknown_steps_keep_their_name() {
+        let step = ConversationStep::from("reranking");
+
+        assert_eq!(step, ConversationStep::Other("reranking".to_string()));
+        assert_eq!(step.to_string(), "reranking");
+    }
+
+    #[tokio::test]
+    async fn clearing_the_session_keeps_leading_system_messages() {
+        let session = session().await;
+        let message = |role, content: &str| Message {
+            role,
+            content: content.to_string(),
+        };
+        session.messages.write().await.extend([
+            message(Role::System, "You are a shop assistant"),
+            message(Role::System, "Answer briefly"),
+            message(Role::User, "red shoes"),
+            message(Role::Assistant, "Here you go"),
+            message(Role::System, "Mid-conversation note"),
+        ]);
+        session
+            .state
+            .write()
+            .await
+            .push(Interaction::new("i".to_string(), "red shoes".to_string()));
+
+        session.clear_session_keep_system().await;
+
+        let contents: Vec<String> = session
+            .get_messages()
+            .await
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(contents, ["You are a shop assistant", "Answer briefly"]);
+        assert_eq!(session.interaction_count().await, 0);
+    }
+
+    #[test]
+    fn answer_config_validation_boundaries() {
+        let similarity = |value: f64| AnswerConfig::new("q").with_min_similarity(value).validate();
+        assert!(similarity(0.0).is_ok());
+        assert!(similarity(1.0).is_ok());
+        assert!(matches!(similarity(-0.01), Err(OramaError::Config { .. })));
+        assert!(matches!(similarity(1.01), Err(OramaError::Config { .. })));
+        assert!(matches!(
+            similarity(f64::NAN),
+            Err(OramaError::Config { .. })
+        ));
+
+        let max_documents =
+            |value: u32| AnswerConfig::new("q").with_max_documents(value).validate();
+        assert!(max_documents(1).is_ok());
+        assert!(max_documents(u32::MAX).is_ok());
+        assert!(matches!(max_documents(0), Err(OramaError::Config { .. })));
+
+        assert!(AnswerConfig::new("q").validate().is_ok());
+    }
+
+    #[test]
+    fn advanced_autoquery_plan_is_parsed() {
+        let mut interaction = Interaction::new("i".to_string(), "q".to_string());
+        assert!(interaction.advanced_autoquery_plan().unwrap().is_none());
+
+        interaction.advanced_autoquery = Some(serde_json::json!({
+            "sub_queries": [{
+                "index": 0,
+                "original_query": "red shoes under 50",
+                "generated_query": { "term": "red shoes" }
+            }],
+            "selected_properties": {
+                "products": { "color": ["red"] }
+            }
+        }));
+        let plan = interaction.advanced_autoquery_plan().unwrap().unwrap();
+
+        assert_eq!(plan.sub_queries.len(), 1);
+        assert_eq!(plan.sub_queries[0].original_query, "red shoes under 50");
+        assert_eq!(plan.sub_queries[0].generated_query["term"], "red shoes");
+        assert_eq!(
+            plan.selected_properties["products"]["color"],
+            [serde_json::json!("red")]
+        );
+    }
+
+    #[test]
+    fn malformed_advanced_autoquery_plans_are_errors() {
+        let mut interaction = Interaction::new("i".to_string(), "q".to_string());
+        interaction.advanced_autoquery = Some(serde_json::Value::Null);
+        assert!(interaction.advanced_autoquery_plan().unwrap().is_none());
+
+        interaction.advanced_autoquery = Some(serde_json::json!({ "sub_queries": "nope" }));
+        assert!(matches!(
+            interaction.advanced_autoquery_plan(),
+            Err(OramaError::Deserialize { .. })
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 //! Core types for the Orama client.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
@@ -8,8 +9,9 @@ use serde::{Deserialize, Serialize};
 pub type AnyObject = serde_json::Value;
 
 /// Supported languages for search operations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum Language {
     Arabic,
     Bulgarian,
@@ -46,8 +48,99 @@ pub enum Language {
     Ukrainian,
 }
 
+impl Language {
+    /// Every supported language
+    pub const ALL: [Language; 33] = [
+        Self::Arabic,
+        Self::Bulgarian,
+        Self::Chinese,
+        Self::Danish,
+        Self::Dutch,
+        Self::German,
+        Self::Greek,
+        Self::English,
+        Self::Estonian,
+        Self::Spanish,
+        Self::Finnish,
+        Self::French,
+        Self::Irish,
+        Self::Hindi,
+        Self::Hungarian,
+        Self::Armenian,
+        Self::Indonesian,
+        Self::Italian,
+        Self::Japanese,
+        Self::Korean,
+        Self::Lithuanian,
+        Self::Nepali,
+        Self::Norwegian,
+        Self::Portuguese,
+        Self::Romanian,
+        Self::Russian,
+        Self::Sanskrit,
+        Self::Slovenian,
+        Self::Serbian,
+        Self::Swedish,
+        Self::Tamil,
+        Self::Turkish,
+        Self::Ukrainian,
+    ];
+
+    /// ISO 639-1 code of the language
+    pub fn as_iso_code(&self) -> &'static str {
+        match self {
+            Self::Arabic => "ar",
+            Self::Bulgarian => "bg",
+            Self::Chinese => "zh",
+            Self::Danish => "da",
+            Self::Dutch => "nl",
+            Self::German => "de",
+            Self::Greek => "el",
+            Self::English => "en",
+            Self::Estonian => "et",
+            Self::Spanish => "es",
+            Self::Finnish => "fi",
+            Self::French => "fr",
+            Self::Irish => "ga",
+            Self::Hindi => "hi",
+            Self::Hungarian => "hu",
+            Self::Armenian => "hy",
+            Self::Indonesian => "id",
+            Self::Italian => "it",
+            Self::Japanese => "ja",
+            Self::Korean => "ko",
+            Self::Lithuanian => "lt",
+            Self::Nepali => "ne",
+            Self::Norwegian => "no",
+            Self::Portuguese => "pt",
+            Self::Romanian => "ro",
+            Self::Russian => "ru",
+            Self::Sanskrit => "sa",
+            Self::Slovenian => "sl",
+            Self::Serbian => "sr",
+            Self::Swedish => "sv",
+            Self::Tamil => "ta",
+            Self::Turkish => "tr",
+            Self::Ukrainian => "uk",
+        }
+    }
+
+    /// Look up a language by ISO 639-1 code, case-insensitively. A region
+    /// subtag such as `en-US` is ignored, and `nb` is read as Norwegian.
+    pub fn from_iso_code(code: &str) -> Option<Self> {
+        let primary = code.split(['-', '_']).next().unwrap_or_default();
+        if primary.eq_ignore_ascii_case("nb") {
+            return Some(Self::Norwegian);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|language| primary.eq_ignore_ascii_case(language.as_iso_code()))
+    }
+}
+
 /// Supported embeddings models
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum EmbeddingsModel {
     #[serde(rename = "E5MultilangualSmall")]
     E5MultilangualSmall,
@@ -64,24 +157,108 @@ pub enum EmbeddingsModel {
 }
 
 /// Embeddings configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct EmbeddingsConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<EmbeddingsModel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub document_fields: Option<Vec<String>>,
 }
 
+impl EmbeddingsConfig {
+    /// Create a new, empty EmbeddingsConfig
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the embeddings model
+    pub fn with_model(mut self, model: EmbeddingsModel) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    /// Set the document fields to embed
+    pub fn with_document_fields(mut self, fields: Vec<String>) -> Self {
+        self.document_fields = Some(fields);
+        self
+    }
+}
+
 /// Hook types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
 pub enum Hook {
-    #[serde(rename = "BeforeAnswer")]
     BeforeAnswer,
-    #[serde(rename = "BeforeRetrieval")]
     BeforeRetrieval,
+    AfterRetrieval,
+    BeforeInsert,
+    /// A hook this client does not know about, kept by name
+    Other(String),
+}
+
+impl Hook {
+    /// The hook name as used by the server
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::BeforeAnswer => "BeforeAnswer",
+            Self::BeforeRetrieval => "BeforeRetrieval",
+            Self::AfterRetrieval => "AfterRetrieval",
+            Self::BeforeInsert => "BeforeInsert",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+impl From<&str> for Hook {
+    fn from(name: &str) -> Self {
+        match name {
+            "BeforeAnswer" => Self::BeforeAnswer,
+            "BeforeRetrieval" => Self::BeforeRetrieval,
+            "AfterRetrieval" => Self::AfterRetrieval,
+            "BeforeInsert" => Self::BeforeInsert,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for Hook {
+    fn from(name: String) -> Self {
+        Self::from(name.as_str())
+    }
+}
+
+impl From<Hook> for String {
+    fn from(hook: Hook) -> Self {
+        hook.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for Hook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Search modes
+///
+/// New modes may be added in minor releases, so matches need a wildcard arm:
+///
+/// ```compile_fail
+/// use oramacore_client::SearchMode;
+///
+/// fn describe(mode: SearchMode) -> &'static str {
+///     match mode {
+///         SearchMode::Fulltext => "fulltext",
+///         SearchMode::Vector => "vector",
+///         SearchMode::Hybrid => "hybrid",
+///         SearchMode::Auto => "auto",
+///     }
+/// }
+/// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum SearchMode {
     Fulltext,
     Vector,
@@ -101,6 +278,10 @@ pub struct SearchParams {
     pub offset: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub properties: Option<Vec<String>>,
+    /// Embedded fields to compare against in vector search; unlike
+    /// `properties`, which only applies to full-text search
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector_properties: Option<Vec<String>>,
     #[serde(rename = "where", skip_serializing_if = "Option::is_none")]
     pub where_clause: Option<AnyObject>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -117,6 +298,164 @@ pub struct SearchParams {
     pub tolerance: Option<u32>,
     #[serde(rename = "userID", skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boost: Option<HashMap<String, f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hybrid_weights: Option<HybridWeights>,
+    /// Opaque cursor from a previous result's `next_cursor`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Document fields to return in hits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_properties: Option<FieldSelection>,
+    /// Hints for the server when `mode` is `Auto` (ignored for other modes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_config: Option<AutoSearchConfig>,
+    /// Options not modeled by this crate yet, sent as top-level body fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Body field names of the typed `SearchParams` fields, which `extra` must not reuse
+const SEARCH_PARAMS_FIELDS: [&str; 19] = [
+    "term",
+    "mode",
+    "limit",
+    "offset",
+    "properties",
+    "vector_properties",
+    "where",
+    "facets",
+    "indexes",
+    "datasourceIDs",
+    "exact",
+    "threshold",
+    "tolerance",
+    "userID",
+    "boost",
+    "hybrid_weights",
+    "cursor",
+    "return_properties",
+    "auto_config",
+];
+
+/// Hints that nudge how `SearchMode::Auto` picks a strategy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AutoSearchConfig {
+    /// Mode to favor when the choice is close
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefer: Option<SearchMode>,
+    /// Weighting applied if hybrid search is chosen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hybrid_weights: Option<HybridWeights>,
+}
+
+impl AutoSearchConfig {
+    /// Create an empty auto search config
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Favor a mode when the choice is close
+    pub fn with_prefer(mut self, mode: SearchMode) -> Self {
+        self.prefer = Some(mode);
+        self
+    }
+
+    /// Set the weighting applied if hybrid search is chosen
+    pub fn with_hybrid_weights(mut self, weights: HybridWeights) -> Self {
+        self.hybrid_weights = Some(weights);
+        self
+    }
+}
+
+/// Selection of document fields returned in search hits.
+///
+/// Hits then contain partial documents, so the document type must tolerate
+/// missing fields (e.g. with `#[serde(default)]`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldSelection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude: Option<Vec<String>>,
+}
+
+impl FieldSelection {
+    /// Return only the given fields
+    pub fn include<S: Into<String>>(fields: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            include: Some(fields.into_iter().map(Into::into).collect()),
+            exclude: None,
+        }
+    }
+
+    /// Return every field except the given ones
+    pub fn exclude<S: Into<String>>(fields: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            include: None,
+            exclude: Some(fields.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+/// Bounds enforced by [`SearchParams::validate_with`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchLimits {
+    /// Largest accepted `limit`
+    pub max_limit: u32,
+    /// Offsets above this log a warning, or fail when `reject_large_offsets` is set
+    pub max_offset: u32,
+    pub reject_large_offsets: bool,
+}
+
+impl SearchLimits {
+    /// Create the default search limits
+    pub fn new() -> Self {
+        Self {
+            max_limit: 1000,
+            max_offset: 10_000,
+            reject_large_offsets: false,
+        }
+    }
+
+    /// Set the largest accepted `limit`
+    pub fn with_max_limit(mut self, max_limit: u32) -> Self {
+        self.max_limit = max_limit;
+        self
+    }
+
+    /// Set the offset above which searches are warned about or rejected
+    pub fn with_max_offset(mut self, max_offset: u32) -> Self {
+        self.max_offset = max_offset;
+        self
+    }
+
+    /// Fail instead of warning when the offset exceeds `max_offset`
+    pub fn with_reject_large_offsets(mut self, reject: bool) -> Self {
+        self.reject_large_offsets = reject;
+        self
+    }
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Blend between full-text and vector scores in hybrid search
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HybridWeights {
+    pub text: f64,
+    pub vector: f64,
+}
+
+impl HybridWeights {
+    /// Create new hybrid weights
+    pub fn new(text: f64, vector: f64) -> Self {
+        Self { text, vector }
+    }
 }
 
 /// Cloud search parameters (omits indexes field)
@@ -132,6 +471,15 @@ pub struct Hit<T = AnyObject> {
     pub datasource_id: Option<String>,
 }
 
+/// Document reordered by a rerank call, with its relevance score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedHit<T = AnyObject> {
+    /// Position of the document in the input
+    pub index: usize,
+    pub score: f64,
+    pub document: T,
+}
+
 /// Elapsed time information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Elapsed {
@@ -148,6 +496,212 @@ pub struct SearchResult<T = AnyObject> {
     pub facets: Option<AnyObject>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub elapsed: Option<Elapsed>,
+    /// Cursor for the next page, absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Mode the server actually used, when it reports it (e.g. for `Auto`)
+    #[serde(
+        default,
+        alias = "mode",
+        deserialize_with = "deserialize_lenient_mode",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub resolved_mode: Option<SearchMode>,
+}
+
+/// Deserialize an optional search mode, treating unknown values as absent
+fn deserialize_lenient_mode<'de, D>(deserializer: D) -> Result<Option<SearchMode>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|value| serde_json::from_value(value).ok()))
+}
+
+impl<T> SearchResult<T> {
+    /// Iterate over the documents of the hits
+    pub fn documents(&self) -> impl Iterator<Item = &T> {
+        self.hits.iter().map(|hit| &hit.document)
+    }
+
+    /// Consume the result, returning the documents of the hits
+    pub fn into_documents(self) -> Vec<T> {
+        self.hits.into_iter().map(|hit| hit.document).collect()
+    }
+
+    /// Whether the result contains no hits
+    pub fn is_empty(&self) -> bool {
+        self.hits.is_empty()
+    }
+
+    /// Number of hits in this result (not the total match count)
+    pub fn len(&self) -> usize {
+        self.hits.len()
+    }
+
+    /// The highest-scoring hit
+    pub fn best(&self) -> Option<&Hit<T>> {
+        self.hits.iter().max_by(|a, b| a.score.total_cmp(&b.score))
+    }
+
+    /// Drop hits scoring below `min`, reducing `count` by the number removed.
+    ///
+    /// This is a local cutoff, independent of the server-side `threshold`.
+    pub fn filter_min_score(&mut self, min: f64) {
+        let before = self.hits.len();
+        self.hits.retain(|hit| hit.score >= min);
+        let removed = (before - self.hits.len()) as u32;
+        self.count = self.count.saturating_sub(removed);
+    }
+
+    /// Group hits by their datasource, preserving hit order within each group.
+    ///
+    /// Hits without a `datasource_id` are omitted.
+    pub fn group_by_datasource(&self) -> HashMap<String, Vec<&Hit<T>>> {
+        let mut groups: HashMap<String, Vec<&Hit<T>>> = HashMap::new();
+        for hit in &self.hits {
+            if let Some(datasource_id) = &hit.datasource_id {
+                groups.entry(datasource_id.clone()).or_default().push(hit);
+            }
+        }
+        groups
+    }
+
+    /// Parse the facets into string and numeric range results.
+    ///
+    /// Facets whose shape is not recognized are skipped.
+    pub fn typed_facets(&self) -> HashMap<String, FacetResult> {
+        self.facets
+            .as_ref()
+            .and_then(|facets| facets.as_object())
+            .map(|facets| {
+                facets
+                    .iter()
+                    .filter_map(|(name, value)| Some((name.clone(), FacetResult::parse(value)?)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Value counts of a string facet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StringFacetResult {
+    #[serde(default)]
+    pub count: u64,
+    #[serde(default)]
+    pub values: HashMap<String, u64>,
+}
+
+/// Document count for one numeric range; a missing bound is open-ended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RangeBucket {
+    #[serde(default)]
+    pub from: Option<f64>,
+    #[serde(default)]
+    pub to: Option<f64>,
+    pub count: u64,
+}
+
+/// Buckets of a numeric range facet, ordered by lower bound
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NumericFacetResult {
+    pub ranges: Vec<RangeBucket>,
+}
+
+/// A facet in a search result
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum FacetResult {
+    String(StringFacetResult),
+    Numeric(NumericFacetResult),
+}
+
+impl FacetResult {
+    /// Parse a single facet value.
+    ///
+    /// Accepts an explicit `{"ranges": [...]}` list, or a `values` map whose
+    /// keys are all ranges such as `"0-100"` (numeric), or any other `values`
+    /// map (string).
+    pub fn parse(value: &AnyObject) -> Option<Self> {
+        if value.get("ranges").is_some() {
+            let mut numeric: NumericFacetResult = serde_json::from_value(value.clone()).ok()?;
+            numeric.sort();
+            return Some(Self::Numeric(numeric));
+        }
+
+        let facet: StringFacetResult = serde_json::from_value(value.clone()).ok()?;
+        let ranges: Option<Vec<RangeBucket>> = if facet.values.is_empty() {
+            None
+        } else {
+            facet
+                .values
+                .iter()
+                .map(|(key, count)| {
+                    let (from, to) = parse_range_key(key)?;
+                    Some(RangeBucket {
+                        from,
+                        to,
+                        count: *count,
+                    })
+                })
+                .collect()
+        };
+
+        Some(match ranges {
+            Some(ranges) => {
+                let mut numeric = NumericFacetResult { ranges };
+                numeric.sort();
+                Self::Numeric(numeric)
+            }
+            None => Self::String(facet),
+        })
+    }
+
+    /// The numeric ranges, if this is a numeric facet
+    pub fn as_numeric(&self) -> Option<&NumericFacetResult> {
+        match self {
+            Self::Numeric(numeric) => Some(numeric),
+            Self::String(_) => None,
+        }
+    }
+
+    /// The value counts, if this is a string facet
+    pub fn as_string(&self) -> Option<&StringFacetResult> {
+        match self {
+            Self::String(facet) => Some(facet),
+            Self::Numeric(_) => None,
+        }
+    }
+}
+
+impl NumericFacetResult {
+    fn sort(&mut self) {
+        self.ranges.sort_by(|a, b| {
+            a.from
+                .unwrap_or(f64::NEG_INFINITY)
+                .total_cmp(&b.from.unwrap_or(f64::NEG_INFINITY))
+        });
+    }
+}
+
+/// Split a range key like `"0-100"`, `"-5--1"` or `"100-"` into its bounds
+fn parse_range_key(key: &str) -> Option<(Option<f64>, Option<f64>)> {
+    let bound = |s: &str| -> Option<Option<f64>> {
+        let s = s.trim();
+        if s.is_empty() || s == "*" {
+            Some(None)
+        } else {
+            s.parse::<f64>().ok().filter(|n| n.is_finite()).map(Some)
+        }
+    };
+
+    key.char_indices()
+        .filter(|(i, c)| *c == '-' && *i > 0)
+        .find_map(|(i, _)| {
+            let (from, to) = (bound(&key[..i])?, bound(&key[i + 1..])?);
+            (from.is_some() || to.is_some()).then_some((from, to))
+        })
 }
 
 /// Trigger definition
@@ -222,14 +776,16 @@ pub struct SystemPrompt {
     pub id: String,
     pub name: String,
     pub prompt: String,
+    #[serde(default)]
     pub usage_mode: SystemPromptUsageMode,
 }
 
 /// System prompt usage modes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum SystemPromptUsageMode {
     Automatic,
+    #[default]
     Manual,
 }
 
@@ -274,6 +830,30 @@ pub struct OverallAssessment {
     pub summary: String,
 }
 
+/// Tool validation response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolValidationResponse {
+    pub security: SecurityValidation,
+    pub schema: SchemaValidation,
+    pub overall_assessment: OverallAssessment,
+}
+
+/// Validation result for a tool's parameters schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaValidation {
+    pub valid: bool,
+    pub reason: String,
+    #[serde(default)]
+    pub parameter_issues: Vec<ParameterIssue>,
+}
+
+/// Problem found with a single tool parameter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterIssue {
+    pub parameter: String,
+    pub issue: String,
+}
+
 /// Tool definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tool {
@@ -285,6 +865,25 @@ pub struct Tool {
     pub system_prompt: Option<String>,
 }
 
+#[cfg(feature = "schemars")]
+impl Tool {
+    /// Build an `InsertToolBody` whose parameters schema is derived from `T`
+    pub fn from_type<T: schemars::JsonSchema>(
+        id: impl Into<String>,
+        description: impl Into<String>,
+    ) -> crate::error::Result<InsertToolBody> {
+        let schema = schemars::schema_for!(T);
+
+        Ok(InsertToolBody {
+            id: id.into(),
+            description: description.into(),
+            parameters: serde_json::to_value(schema)?,
+            code: None,
+            system_prompt: None,
+        })
+    }
+}
+
 /// Request body for inserting a tool
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InsertToolBody {
@@ -316,6 +915,16 @@ pub struct FunctionCall {
     pub arguments: String,
 }
 
+impl FunctionCall {
+    /// Deserialize the call arguments into a typed value
+    pub fn parsed_arguments<T>(&self) -> crate::error::Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Ok(serde_json::from_str(&self.arguments)?)
+    }
+}
+
 /// Parsed function call
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionCallParsed {
@@ -357,12 +966,24 @@ pub struct FunctionParametersData<T = AnyObject> {
     pub result: T,
 }
 
-/// Execute tools result (union type)
+/// Execute tools result, tagged by its wrapper key (`functionResult` or
+/// `functionParameters`)
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
 pub enum ExecuteToolsResult<T = AnyObject> {
-    FunctionResult(ExecuteToolsFunctionResult<T>),
-    ParametersResult(ExecuteToolsParametersResult<T>),
+    #[serde(rename = "functionResult")]
+    FunctionResult(FunctionResultData<T>),
+    #[serde(rename = "functionParameters")]
+    ParametersResult(FunctionParametersData<T>),
+}
+
+impl<T> ExecuteToolsResult<T> {
+    /// ID of the tool that produced this result
+    pub fn tool_id(&self) -> &str {
+        match self {
+            Self::FunctionResult(data) => &data.tool_id,
+            Self::ParametersResult(data) => &data.tool_id,
+        }
+    }
 }
 
 /// Parsed execute tools response
@@ -373,32 +994,59 @@ pub struct ExecuteToolsParsedResponse<T = AnyObject> {
 
 /// NLP search result
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NlpSearchResult<T> {
+pub struct NlpSearchResult<T = AnyObject> {
     pub original_query: String,
     pub generated_query: SearchParams,
-    pub results: Vec<HashMap<String, serde_json::Value>>,
-    #[serde(skip)]
-    _phantom: std::marker::PhantomData<T>,
+    pub results: Vec<Hit<T>>,
 }
 
 impl<T> NlpSearchResult<T> {
     pub fn new(
         original_query: String,
         generated_query: SearchParams,
-        results: Vec<HashMap<String, serde_json::Value>>,
+        results: Vec<Hit<T>>,
     ) -> Self {
         Self {
             original_query,
             generated_query,
             results,
-            _phantom: std::marker::PhantomData,
         }
     }
+
+    /// Execute the generated query directly as a regular search
+    pub async fn re_run(
+        &self,
+        manager: &crate::collection::CollectionManager,
+    ) -> crate::error::Result<SearchResult<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        manager.search(&self.generated_query).await
+    }
+
+    /// Deserialize each hit's document into `U`.
+    ///
+    /// Rows are converted independently, so one malformed document yields an
+    /// `Err` in its slot without failing the others.
+    pub fn typed_results<U>(&self) -> Vec<crate::error::Result<U>>
+    where
+        T: Serialize,
+        U: serde::de::DeserializeOwned,
+    {
+        self.results
+            .iter()
+            .map(|hit| {
+                let value = serde_json::to_value(&hit.document)?;
+                Ok(serde_json::from_value(value)?)
+            })
+            .collect()
+    }
 }
 
 /// NLP search stream status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[non_exhaustive]
 pub enum NlpSearchStreamStatus {
     Init,
     OptimizingQuery,
@@ -426,6 +1074,16 @@ pub struct GeneratedQuery {
 /// Selected properties information
 pub type SelectedProperties = HashMap<String, HashMap<String, Vec<serde_json::Value>>>;
 
+/// Query plan produced by advanced autoquery: the sub-queries generated from
+/// the user's question and the properties selected to filter them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdvancedAutoquery {
+    #[serde(default)]
+    pub sub_queries: Vec<GeneratedQuery>,
+    #[serde(default)]
+    pub selected_properties: SelectedProperties,
+}
+
 /// Combined query and properties
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CombinedQueryAndProperties {
@@ -462,8 +1120,9 @@ pub struct LlmConfig {
 }
 
 /// LLM providers
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum LlmProvider {
     OpenAI,
     Fireworks,
@@ -472,6 +1131,80 @@ pub enum LlmProvider {
     Claude,
 }
 
+/// Price of a model in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenPricing {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+impl TokenPricing {
+    /// Create new token pricing, in USD per million tokens
+    pub fn new(input_per_million: f64, output_per_million: f64) -> Self {
+        Self {
+            input_per_million,
+            output_per_million,
+        }
+    }
+}
+
+/// Token prices keyed by provider and model.
+///
+/// The default table covers a few common models; prices change, so override
+/// them with [`PricingTable::with_price`] as needed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingTable {
+    prices: HashMap<(LlmProvider, String), TokenPricing>,
+}
+
+impl PricingTable {
+    /// Create an empty pricing table
+    pub fn new() -> Self {
+        Self {
+            prices: HashMap::new(),
+        }
+    }
+
+    /// Set the price of a model, replacing any existing entry
+    pub fn with_price<S: Into<String>>(
+        mut self,
+        provider: LlmProvider,
+        model: S,
+        pricing: TokenPricing,
+    ) -> Self {
+        self.prices.insert((provider, model.into()), pricing);
+        self
+    }
+
+    /// Get the price of a model
+    pub fn get(&self, provider: &LlmProvider, model: &str) -> Option<TokenPricing> {
+        self.prices
+            .get(&(provider.clone(), model.to_string()))
+            .copied()
+    }
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        Self::new()
+            .with_price(LlmProvider::OpenAI, "gpt-4o", TokenPricing::new(2.5, 10.0))
+            .with_price(
+                LlmProvider::OpenAI,
+                "gpt-4o-mini",
+                TokenPricing::new(0.15, 0.6),
+            )
+    }
+}
+
+/// Estimated cost of token usage
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Cost {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Total in USD
+    pub amount: f64,
+}
+
 /// Message role
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -507,6 +1240,72 @@ pub enum RelatedQuestionsFormat {
     Query,
 }
 
+/// What happened in an analytics event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum AnalyticsEventKind {
+    /// A search was performed
+    Search { query: String, result_count: u32 },
+    /// A hit was clicked; `position` is its zero-based rank in the results
+    Click { hit_id: String, position: usize },
+    /// The user converted, optionally attributed to a hit
+    Conversion {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        hit_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<f64>,
+    },
+}
+
+/// User and session that analytics events are attributed to
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticsContext {
+    pub user_id: String,
+    pub session_id: Option<String>,
+}
+
+impl AnalyticsContext {
+    /// Create a context for a user
+    pub fn new<S: Into<String>>(user_id: S) -> Self {
+        Self {
+            user_id: user_id.into(),
+            session_id: None,
+        }
+    }
+
+    /// Set the session id
+    pub fn with_session_id<S: Into<String>>(mut self, session_id: S) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+}
+
+/// Analytics event as submitted to the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalyticsEvent {
+    #[serde(flatten)]
+    pub kind: AnalyticsEventKind,
+    #[serde(rename = "userID")]
+    pub user_id: String,
+    #[serde(rename = "sessionID", skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+impl AnalyticsEvent {
+    /// Create an event for the given context, timestamped now
+    pub fn new(context: &AnalyticsContext, kind: AnalyticsEventKind) -> Self {
+        Self {
+            kind,
+            user_id: context.user_id.clone(),
+            session_id: context.session_id.clone(),
+            timestamp: crate::utils::current_time_millis(),
+        }
+    }
+}
+
 // Implementation methods for SearchParams
 impl SearchParams {
     /// Create a new SearchParams with a term
@@ -517,6 +1316,7 @@ impl SearchParams {
             limit: None,
             offset: None,
             properties: None,
+            vector_properties: None,
             where_clause: None,
             facets: None,
             indexes: None,
@@ -525,6 +1325,12 @@ impl SearchParams {
             threshold: None,
             tolerance: None,
             user_id: None,
+            boost: None,
+            hybrid_weights: None,
+            cursor: None,
+            return_properties: None,
+            auto_config: None,
+            extra: HashMap::new(),
         }
     }
 
@@ -546,6 +1352,15 @@ impl SearchParams {
         self
     }
 
+    /// Request a page of results; `page` is 0-indexed.
+    ///
+    /// Sets `offset = page * page_size` and `limit = page_size`. A `page_size`
+    /// of 0 is rejected when the search is validated.
+    pub fn with_page(self, page: u32, page_size: u32) -> Self {
+        self.with_offset(page.saturating_mul(page_size))
+            .with_limit(page_size)
+    }
+
     /// Set exact matching
     pub fn with_exact(mut self, exact: bool) -> Self {
         self.exact = Some(exact);
@@ -564,6 +1379,12 @@ impl SearchParams {
         self
     }
 
+    /// Set the where clause from a typed filter
+    pub fn with_filter(mut self, filter: crate::filter::Filter) -> Self {
+        self.where_clause = Some(filter.to_where());
+        self
+    }
+
     /// Set facets
     pub fn with_facets(mut self, facets: AnyObject) -> Self {
         self.facets = Some(facets);
@@ -575,7 +1396,770 @@ impl SearchParams {
         self.properties = Some(properties);
         self
     }
+
+    /// Restrict vector search to these embedded fields.
+    ///
+    /// In hybrid mode only the vector half is restricted; the full-text half
+    /// still uses `properties`. Ignored in full-text mode.
+    pub fn with_vector_properties(mut self, properties: Vec<String>) -> Self {
+        self.vector_properties = Some(properties);
+        self
+    }
+
+    /// Set hints for the `Auto` mode (ignored for other modes)
+    pub fn with_auto_config(mut self, config: AutoSearchConfig) -> Self {
+        self.auto_config = Some(config);
+        self
+    }
+
+    /// Select the document fields returned in hits
+    pub fn with_return_properties(mut self, selection: FieldSelection) -> Self {
+        self.return_properties = Some(selection);
+        self
+    }
+
+    /// Continue from a cursor returned by a previous search
+    pub fn with_cursor<S: Into<String>>(mut self, cursor: S) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// Set the text/vector blend for hybrid search (ignored for other modes)
+    pub fn with_hybrid_weights(mut self, weights: HybridWeights) -> Self {
+        self.hybrid_weights = Some(weights);
+        self
+    }
+
+    /// Overlay `overrides` on these parameters, e.g. per-request values on a
+    /// query template.
+    ///
+    /// Each field set in `overrides` wins; `term` is taken from `overrides`
+    /// unless it is empty. Maps and lists are replaced, not combined, except
+    /// `extra`, which is merged key by key.
+    pub fn merge(&self, overrides: &SearchParams) -> SearchParams {
+        // Destructure so that new fields cannot be forgotten here
+        let SearchParams {
+            term,
+            mode,
+            limit,
+            offset,
+            properties,
+            vector_properties,
+            where_clause,
+            facets,
+            indexes,
+            datasource_ids,
+            exact,
+            threshold,
+            tolerance,
+            user_id,
+            boost,
+            hybrid_weights,
+            cursor,
+            return_properties,
+            auto_config,
+            extra,
+        } = overrides.clone();
+
+        let base = self.clone();
+        SearchParams {
+            term: if term.is_empty() { base.term } else { term },
+            mode: mode.or(base.mode),
+            limit: limit.or(base.limit),
+            offset: offset.or(base.offset),
+            properties: properties.or(base.properties),
+            vector_properties: vector_properties.or(base.vector_properties),
+            where_clause: where_clause.or(base.where_clause),
+            facets: facets.or(base.facets),
+            indexes: indexes.or(base.indexes),
+            datasource_ids: datasource_ids.or(base.datasource_ids),
+            exact: exact.or(base.exact),
+            threshold: threshold.or(base.threshold),
+            tolerance: tolerance.or(base.tolerance),
+            user_id: user_id.or(base.user_id),
+            boost: boost.or(base.boost),
+            hybrid_weights: hybrid_weights.or(base.hybrid_weights),
+            cursor: cursor.or(base.cursor),
+            return_properties: return_properties.or(base.return_properties),
+            auto_config: auto_config.or(base.auto_config),
+            extra: base.extra.into_iter().chain(extra).collect(),
+        }
+    }
+
+    /// Pass an option not modeled by this crate yet.
+    ///
+    /// The key must not be the body name of a typed field (e.g. `limit`);
+    /// such collisions are rejected when the search is sent.
+    pub fn with_extra<K: Into<String>, V: Into<serde_json::Value>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+
+    /// Validate parameters that the server would otherwise reject, using the
+    /// default [`SearchLimits`]
+    pub fn validate(&self) -> crate::error::Result<()> {
+        self.validate_with(&SearchLimits::default())
+    }
+
+    /// Validate parameters against the given limits
+    pub fn validate_with(&self, limits: &SearchLimits) -> crate::error::Result<()> {
+        if let Some(limit) = self.limit {
+            if limit == 0 {
+                return Err(crate::error::OramaError::config(
+                    "Search limit must be greater than 0",
+                ));
+            }
+            if limit > limits.max_limit {
+                return Err(crate::error::OramaError::config(format!(
+                    "Search limit {limit} exceeds the maximum of {}",
+                    limits.max_limit
+                )));
+            }
+        }
+
+        if let Some(offset) = self.offset.filter(|offset| *offset > limits.max_offset) {
+            let message = format!(
+                "Search offset {offset} exceeds {}; deep offset pagination is slow, \
+                 page with `with_cursor` or `search_pages` instead",
+                limits.max_offset
+            );
+            if limits.reject_large_offsets {
+                return Err(crate::error::OramaError::config(message));
+            }
+            warn!("{message}");
+        }
+
+        if let Some(key) = self
+            .extra
+            .keys()
+            .find(|key| SEARCH_PARAMS_FIELDS.contains(&key.as_str()))
+        {
+            return Err(crate::error::OramaError::generic(format!(
+                "Extra search option \"{key}\" collides with a typed field; set the field instead"
+            )));
+        }
+
+        if let Some(weights) = &self.hybrid_weights {
+            if weights.text < 0.0 || weights.vector < 0.0 {
+                return Err(crate::error::OramaError::generic(
+                    "Hybrid weights must be non-negative",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate and prepare the parameters for sending, dropping options that
+    /// do not apply to the selected mode
+    pub(crate) fn for_request(
+        &self,
+        limits: &SearchLimits,
+    ) -> crate::error::Result<Cow<'_, SearchParams>> {
+        self.validate_with(limits)?;
+
+        let strip_weights = self.hybrid_weights.is_some() && self.mode != Some(SearchMode::Hybrid);
+        let strip_auto = self.auto_config.is_some() && self.mode != Some(SearchMode::Auto);
+
+        if strip_weights || strip_auto {
+            let mut params = self.clone();
+            if strip_weights {
+                params.hybrid_weights = None;
+            }
+            if strip_auto {
+                params.auto_config = None;
+            }
+            return Ok(Cow::Owned(params));
+        }
+
+        Ok(Cow::Borrowed(self))
+    }
+
+    /// Boost the relevance of a field by a factor
+    pub fn with_boost<S: Into<String>>(mut self, field: S, factor: f64) -> Self {
+        self.boost
+            .get_or_insert_with(HashMap::new)
+            .insert(field.into(), factor);
+        self
+    }
 }
 
 /// Default server user ID for server-side operations
 pub const DEFAULT_SERVER_USER_ID: &str = "server-user-default";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::OramaError;
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct Product {
+        title: String,
+        price: f64,
+    }
+
+    fn hit(id: &str, score: f64, datasource_id: Option<&str>) -> Hit<String> {
+        Hit {
+            id: id.to_string(),
+            score,
+            document: format!("doc-{id}"),
+            datasource_id: datasource_id.map(String::from),
+        }
+    }
+
+    fn result(hits: Vec<Hit<String>>) -> SearchResult<String> {
+        SearchResult {
+            count: hits.len() as u32,
+            hits,
+            facets: None,
+            elapsed: None,
+            next_cursor: None,
+            resolved_mode: None,
+        }
+    }
+
+    #[test]
+    fn nlp_search_results_deserialize_into_typed_hits() {
+        let result: NlpSearchResult<Product> = serde_json::from_value(serde_json::json!({
+            "original_query": "cheap shoes",
+            "generated_query": { "term": "shoes", "where": { "price": { "lt": 50 } } },
+            "results": [
+                { "id": "1", "score": 0.9, "document": { "title": "Sneaker", "price": 30.0 } }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(result.generated_query.term, "shoes");
+        assert_eq!(
+            result.generated_query.where_clause,
+            Some(serde_json::json!({ "price": { "lt": 50 } }))
+        );
+        assert_eq!(result.results[0].id, "1");
+        assert_eq!(
+            result.results[0].document,
+            Product {
+                title: "Sneaker".to_string(),
+                price: 30.0,
+            }
+        );
+    }
+
+    #[test]
+    fn search_result_accessors() {
+        let result = result(vec![
+            hit("a", 0.4, None),
+            hit("b", 0.9, None),
+            hit("c", 0.1, None),
+        ]);
+
+        assert!(!result.is_empty());
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.best().unwrap().id, "b");
+        assert_eq!(
+            result.documents().collect::<Vec<_>>(),
+            ["doc-a", "doc-b", "doc-c"]
+        );
+        assert_eq!(result.into_documents(), ["doc-a", "doc-b", "doc-c"]);
+    }
+
+    #[test]
+    fn empty_search_result_accessors() {
+        let result = result(Vec::new());
+
+        assert!(result.is_empty());
+        assert_eq!(result.len(), 0);
+        assert!(result.best().is_none());
+        assert_eq!(result.documents().count(), 0);
+    }
+
+    #[test]
+    fn boosts_serialize_alongside_properties() {
+        let params = SearchParams::new("shoes")
+            .with_properties(vec!["title".to_string(), "brand".to_string()])
+            .with_boost("title", 2.0)
+            .with_boost("brand", 1.5);
+
+        let body = serde_json::to_value(&params).unwrap();
+
+        assert_eq!(body["properties"], serde_json::json!(["title", "brand"]));
+        assert_eq!(
+            body["boost"],
+            serde_json::json!({ "title": 2.0, "brand": 1.5 })
+        );
+    }
+
+    #[test]
+    fn hybrid_weights_are_only_sent_in_hybrid_mode() {
+        let weights = HybridWeights::new(0.3, 0.7);
+        let hybrid = SearchParams::new("q")
+            .with_mode(SearchMode::Hybrid)
+            .with_hybrid_weights(weights);
+        let fulltext = SearchParams::new("q")
+            .with_mode(SearchMode::Fulltext)
+            .with_hybrid_weights(weights);
+
+        let limits = SearchLimits::default();
+        let hybrid = serde_json::to_value(hybrid.for_request(&limits).unwrap()).unwrap();
+        let fulltext = serde_json::to_value(fulltext.for_request(&limits).unwrap()).unwrap();
+
+        assert_eq!(
+            hybrid["hybrid_weights"],
+            serde_json::json!({ "text": 0.3, "vector": 0.7 })
+        );
+        assert!(fulltext.get("hybrid_weights").is_none());
+    }
+
+    #[test]
+    fn negative_hybrid_weights_are_rejected() {
+        let params = |text, vector| {
+            SearchParams::new("q")
+                .with_mode(SearchMode::Hybrid)
+                .with_hybrid_weights(HybridWeights::new(text, vector))
+        };
+
+        assert!(params(0.0, 1.0).validate().is_ok());
+        assert!(params(-0.1, 1.0).validate().is_err());
+        assert!(params(1.0, -0.1).validate().is_err());
+    }
+
+    #[test]
+    fn hits_group_by_datasource() {
+        let result = result(vec![
+            hit("a", 0.9, Some("docs")),
+            hit("b", 0.8, Some("blog")),
+            hit("c", 0.7, Some("docs")),
+            hit("d", 0.6, None),
+        ]);
+
+        let groups = result.group_by_datasource();
+
+        assert_eq!(groups.len(), 2);
+        let docs: Vec<&str> = groups["docs"].iter().map(|h| h.id.as_str()).collect();
+        assert_eq!(docs, ["a", "c"]);
+        assert_eq!(groups["blog"][0].id, "b");
+    }
+
+    #[test]
+    fn filter_min_score_drops_low_hits_and_adjusts_count() {
+        let mut result = result(vec![
+            hit("a", 0.9, None),
+            hit("b", 0.2, None),
+            hit("c", 0.5, None),
+        ]);
+        result.count = 10;
+
+        result.filter_min_score(0.5);
+
+        let ids: Vec<&str> = result.hits.iter().map(|h| h.id.as_str()).collect();
+        assert_eq!(ids, ["a", "c"]);
+        assert_eq!(result.count, 9);
+    }
+
+    #[test]
+    fn limit_validation_boundaries() {
+        let limits = SearchLimits::new().with_max_limit(100);
+        let with_limit = |limit| SearchParams::new("q").with_limit(limit);
+
+        assert!(matches!(
+            with_limit(0).validate_with(&limits),
+            Err(OramaError::Config { .. })
+        ));
+        assert!(with_limit(1).validate_with(&limits).is_ok());
+        assert!(with_limit(100).validate_with(&limits).is_ok());
+        assert!(matches!(
+            with_limit(101).validate_with(&limits),
+            Err(OramaError::Config { .. })
+        ));
+    }
+
+    #[test]
+    fn large_offsets_warn_unless_rejected() {
+        let limits = SearchLimits::new().with_max_offset(500);
+        let with_offset = |offset| SearchParams::new("q").with_offset(offset);
+
+        assert!(with_offset(500).validate_with(&limits).is_ok());
+        assert!(with_offset(501).validate_with(&limits).is_ok());
+
+        let limits = limits.with_reject_large_offsets(true);
+        assert!(with_offset(500).validate_with(&limits).is_ok());
+        let error = with_offset(501).validate_with(&limits).unwrap_err();
+        assert!(matches!(error, OramaError::Config { .. }));
+        assert!(error.to_string().contains("with_cursor"));
+    }
+
+    #[test]
+    fn execute_tools_results_are_tagged_by_wrapper_key() {
+        let response: ExecuteToolsParsedResponse = serde_json::from_value(serde_json::json!({
+            "results": [
+                { "functionResult": { "tool_id": "weather", "result": { "temp": 21 } } },
+                { "functionParameters": { "tool_id": "search", "result": { "term": "shoes" } } }
+            ]
+        }))
+        .unwrap();
+        let results = response.results.unwrap();
+
+        assert!(
+            matches!(&results[0], ExecuteToolsResult::FunctionResult(data) if data.tool_id == "weather")
+        );
+        assert!(
+            matches!(&results[1], ExecuteToolsResult::ParametersResult(data) if data.tool_id == "search")
+        );
+        assert_eq!(results[1].tool_id(), "search");
+    }
+
+    #[test]
+    fn function_parameters_are_not_mistaken_for_function_results() {
+        // Both payloads share `tool_id` and `result`; an untagged enum parsed
+        // this as a `FunctionResult`
+        let result: ExecuteToolsResult = serde_json::from_value(serde_json::json!({
+            "functionParameters": { "tool_id": "search", "result": {} }
+        }))
+        .unwrap();
+
+        assert!(matches!(result, ExecuteToolsResult::ParametersResult(_)));
+        assert!(
+            serde_json::from_value::<ExecuteToolsResult>(serde_json::json!({
+                "tool_id": "search",
+                "result": {}
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn resolved_mode_deserializes_leniently() {
+        let parse = |extra: serde_json::Value| {
+            let mut body = serde_json::json!({ "count": 0, "hits": [] });
+            body.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value::<SearchResult<String>>(body)
+                .unwrap()
+                .resolved_mode
+        };
+
+        assert_eq!(
+            parse(serde_json::json!({ "resolved_mode": "hybrid" })),
+            Some(SearchMode::Hybrid)
+        );
+        assert_eq!(
+            parse(serde_json::json!({ "mode": "vector" })),
+            Some(SearchMode::Vector)
+        );
+        assert_eq!(parse(serde_json::json!({})), None);
+        assert_eq!(parse(serde_json::json!({ "resolved_mode": null })), None);
+        assert_eq!(
+            parse(serde_json::json!({ "resolved_mode": "semantic" })),
+            None
+        );
+    }
+
+    #[test]
+    fn return_properties_serialize_include_and_exclude() {
+        let body = |selection: FieldSelection| {
+            let params = SearchParams::new("q").with_return_properties(selection);
+            serde_json::to_value(params).unwrap()["return_properties"].clone()
+        };
+
+        assert_eq!(
+            body(FieldSelection::include(["title", "price"])),
+            serde_json::json!({ "include": ["title", "price"] })
+        );
+        assert_eq!(
+            body(FieldSelection::exclude(["embedding"])),
+            serde_json::json!({ "exclude": ["embedding"] })
+        );
+        assert!(serde_json::to_value(SearchParams::new("q"))
+            .unwrap()
+            .get("return_properties")
+            .is_none());
+    }
+
+    #[test]
+    fn pages_map_to_offset_and_limit() {
+        for (page, size, offset, limit) in [
+            (0, 10, 0, 10),
+            (1, 10, 10, 10),
+            (3, 25, 75, 25),
+            (u32::MAX, 2, u32::MAX, 2),
+        ] {
+            let params = SearchParams::new("q").with_page(page, size);
+            assert_eq!((params.offset, params.limit), (Some(offset), Some(limit)));
+        }
+
+        assert!(SearchParams::new("q").with_page(2, 0).validate().is_err());
+    }
+
+    #[test]
+    fn auto_config_is_only_sent_in_auto_mode() {
+        let config = AutoSearchConfig::new()
+            .with_prefer(SearchMode::Vector)
+            .with_hybrid_weights(HybridWeights::new(0.5, 0.5));
+        let body = |mode| {
+            let params = SearchParams::new("q")
+                .with_mode(mode)
+                .with_auto_config(config.clone());
+            serde_json::to_value(params.for_request(&SearchLimits::default()).unwrap()).unwrap()
+        };
+
+        assert_eq!(
+            body(SearchMode::Auto)["auto_config"],
+            serde_json::json!({
+                "prefer": "vector",
+                "hybrid_weights": { "text": 0.5, "vector": 0.5 }
+            })
+        );
+        assert!(body(SearchMode::Fulltext).get("auto_config").is_none());
+        assert_eq!(
+            serde_json::to_value(AutoSearchConfig::new()).unwrap(),
+            serde_json::json!({})
+        );
+    }
+
+    fn nlp_result(documents: Vec<serde_json::Value>) -> NlpSearchResult<serde_json::Value> {
+        serde_json::from_value(serde_json::json!({
+            "original_query": "cheap shoes",
+            "generated_query": { "term": "shoes" },
+            "results": documents
+                .into_iter()
+                .enumerate()
+                .map(|(i, document)| serde_json::json!({ "id": i.to_string(), "score": 1.0, "document": document }))
+                .collect::<Vec<_>>()
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn nlp_typed_results_on_clean_rows() {
+        let result = nlp_result(vec![
+            serde_json::json!({ "title": "Sneaker", "price": 30.0 }),
+            serde_json::json!({ "title": "Boot", "price": 80.0, "extra": true }),
+        ]);
+
+        let products: Vec<Product> = result
+            .typed_results()
+            .into_iter()
+            .collect::<crate::error::Result<_>>()
+            .unwrap();
+
+        assert_eq!(products.len(), 2);
+        assert_eq!(products[1].title, "Boot");
+    }
+
+    #[test]
+    fn nlp_typed_results_keep_per_row_errors() {
+        let result = nlp_result(vec![
+            serde_json::json!({ "title": "Sneaker", "price": 30.0 }),
+            serde_json::json!({ "title": "Broken" }),
+            serde_json::json!({ "title": "Sandal", "price": 20.0 }),
+        ]);
+
+        let rows = result.typed_results::<Product>();
+
+        assert!(rows[0].is_ok());
+        assert!(rows[1].is_err());
+        assert_eq!(rows[2].as_ref().unwrap().title, "Sandal");
+    }
+
+    #[test]
+    fn numeric_and_string_facets_are_parsed() {
+        let result: SearchResult<String> = serde_json::from_value(serde_json::json!({
+            "count": 9,
+            "hits": [],
+            "facets": {
+                "price": { "count": 3, "values": { "100-200": 3, "0-100": 5, "200-*": 1 } },
+                "rating": { "ranges": [{ "from": 4.0, "count": 2 }, { "to": 4.0, "count": 7 }] },
+                "delta": { "count": 1, "values": { "-5--1": 4 } },
+                "brand": { "count": 2, "values": { "acme": 6, "zenith": 3 } }
+            }
+        }))
+        .unwrap();
+
+        let facets = result.typed_facets();
+        let bucket = |from, to, count| RangeBucket { from, to, count };
+
+        assert_eq!(
+            facets["price"].as_numeric().unwrap().ranges,
+            [
+                bucket(Some(0.0), Some(100.0), 5),
+                bucket(Some(100.0), Some(200.0), 3),
+                bucket(Some(200.0), None, 1),
+            ]
+        );
+        assert_eq!(
+            facets["rating"].as_numeric().unwrap().ranges,
+            [bucket(None, Some(4.0), 7), bucket(Some(4.0), None, 2)]
+        );
+        assert_eq!(
+            facets["delta"].as_numeric().unwrap().ranges,
+            [bucket(Some(-5.0), Some(-1.0), 4)]
+        );
+        let brand = facets["brand"].as_string().unwrap();
+        assert_eq!(brand.values["acme"], 6);
+        assert!(facets["brand"].as_numeric().is_none());
+    }
+
+    #[test]
+    fn merge_prefers_set_override_fields() {
+        let base = SearchParams::new("template")
+            .with_mode(SearchMode::Fulltext)
+            .with_limit(20)
+            .with_properties(vec!["title".to_string()])
+            .with_where(serde_json::json!({ "in_stock": true }))
+            .with_extra("rerank", true)
+            .with_extra("typo", 1);
+        let overrides = SearchParams::new("shoes")
+            .with_limit(5)
+            .with_extra("typo", 2);
+
+        let merged = base.merge(&overrides);
+
+        assert_eq!(merged.term, "shoes");
+        assert_eq!(merged.limit, Some(5));
+        assert_eq!(merged.mode, Some(SearchMode::Fulltext));
+        assert_eq!(merged.properties, Some(vec!["title".to_string()]));
+        assert_eq!(
+            merged.where_clause,
+            Some(serde_json::json!({ "in_stock": true }))
+        );
+        assert_eq!(merged.offset, None);
+        assert_eq!(merged.extra["rerank"], true);
+        assert_eq!(merged.extra["typo"], 2);
+    }
+
+    #[test]
+    fn merge_keeps_the_base_term_when_the_override_is_empty() {
+        let base = SearchParams::new("template").with_limit(20);
+
+        let merged = base.merge(&SearchParams::new("").with_offset(40));
+
+        assert_eq!(merged.term, "template");
+        assert_eq!((merged.offset, merged.limit), (Some(40), Some(20)));
+    }
+
+    #[test]
+    fn extra_options_serialize_alongside_known_fields() {
+        let params = SearchParams::new("shoes")
+            .with_limit(5)
+            .with_extra("rerank", true)
+            .with_extra("typo_budget", serde_json::json!({ "max": 2 }));
+
+        assert_eq!(
+            serde_json::to_value(&params).unwrap(),
+            serde_json::json!({
+                "term": "shoes",
+                "limit": 5,
+                "rerank": true,
+                "typo_budget": { "max": 2 }
+            })
+        );
+    }
+
+    #[test]
+    fn extra_options_colliding_with_known_fields_are_rejected() {
+        for key in ["limit", "where", "datasourceIDs"] {
+            let params = SearchParams::new("shoes").with_extra(key, 1);
+            assert!(params.validate().is_err(), "{key}");
+        }
+        assert!(SearchParams::new("shoes")
+            .with_extra("rerank", true)
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn hooks_serialize_to_their_server_names() {
+        for (hook, name) in [
+            (Hook::BeforeAnswer, "BeforeAnswer"),
+            (Hook::BeforeRetrieval, "BeforeRetrieval"),
+            (Hook::AfterRetrieval, "AfterRetrieval"),
+            (Hook::BeforeInsert, "BeforeInsert"),
+        ] {
+            assert_eq!(serde_json::to_value(&hook).unwrap(), name);
+            assert_eq!(serde_json::from_value::<Hook>(name.into()).unwrap(), hook);
+        }
+    }
+
+    #[test]
+    fn unknown_hook_names_round_trip() {
+        let hook: Hook = serde_json::from_value("AfterAnswer".into()).unwrap();
+
+        assert_eq!(hook, Hook::Other("AfterAnswer".to_string()));
+        assert_eq!(serde_json::to_value(&hook).unwrap(), "AfterAnswer");
+        assert_eq!(hook.to_string(), "AfterAnswer");
+    }
+
+    #[test]
+    fn vector_properties_serialize_apart_from_properties() {
+        let params = SearchParams::new("q")
+            .with_mode(SearchMode::Hybrid)
+            .with_properties(vec!["title".to_string()])
+            .with_vector_properties(vec!["description".to_string()]);
+        let body = serde_json::to_value(params).unwrap();
+
+        assert_eq!(body["properties"], serde_json::json!(["title"]));
+        assert_eq!(
+            body["vector_properties"],
+            serde_json::json!(["description"])
+        );
+
+        let body = serde_json::to_value(SearchParams::new("q")).unwrap();
+        assert!(body.get("vector_properties").is_none());
+    }
+
+    #[test]
+    fn all_lists_every_language() {
+        // The match has no wildcard arm, so the list stops compiling when a
+        // variant is added, and the comparison fails until `ALL` has it too
+        macro_rules! languages {
+            ($($variant:ident,)*) => {{
+                fn exhaustive(language: &Language) {
+                    match language {
+                        $(Language::$variant => {})*
+                    }
+                }
+                let _ = exhaustive;
+                [$(Language::$variant),*]
+            }};
+        }
+
+        let every = languages!(
+            Arabic, Bulgarian, Chinese, Danish, Dutch, German, Greek, English, Estonian, Spanish,
+            Finnish, French, Irish, Hindi, Hungarian, Armenian, Indonesian, Italian, Japanese,
+            Korean, Lithuanian, Nepali, Norwegian, Portuguese, Romanian, Russian, Sanskrit,
+            Slovenian, Serbian, Swedish, Tamil, Turkish, Ukrainian,
+        );
+        assert_eq!(Language::ALL, every);
+    }
+
+    #[test]
+    fn every_language_round_trips_through_serde_and_iso_codes() {
+        let mut codes = std::collections::HashSet::new();
+        for language in Language::ALL {
+            let json = serde_json::to_value(&language).unwrap();
+            assert_eq!(json, format!("{language:?}").to_lowercase());
+            assert_eq!(serde_json::from_value::<Language>(json).unwrap(), language);
+
+            let code = language.as_iso_code();
+            assert!(codes.insert(code), "duplicate ISO code {code}");
+            assert_eq!(Language::from_iso_code(code), Some(language.clone()));
+            assert_eq!(
+                Language::from_iso_code(&code.to_uppercase()),
+                Some(language)
+            );
+        }
+    }
+
+    #[test]
+    fn iso_codes_ignore_regions_and_reject_unknown_codes() {
+        assert_eq!(Language::from_iso_code("en-US"), Some(Language::English));
+        assert_eq!(Language::from_iso_code("pt_BR"), Some(Language::Portuguese));
+        assert_eq!(Language::from_iso_code("nb"), Some(Language::Norwegian));
+        assert_eq!(Language::from_iso_code("xx"), None);
+        assert_eq!(Language::from_iso_code(""), None);
+        assert!(serde_json::from_str::<Language>(r#""English""#).is_err());
+    }
+}
@@ -4,6 +4,12 @@ use std::time::{Duration, Instant};
 
 use uuid::Uuid;
 
+use crate::error::OramaError;
+use crate::types::Language;
+
+/// Minimum number of non-whitespace characters required for language detection
+const MIN_LANGUAGE_DETECTION_CHARS: usize = 10;
+
 /// Create a random string of specified length
 pub fn create_random_string(length: usize) -> String {
     use uuid::Uuid;
@@ -20,6 +26,24 @@ pub fn create_random_string(length: usize) -> String {
     result
 }
 
+/// Truncate a string to at most `max_chars` characters without splitting a
+/// multibyte character
+pub fn truncate_chars(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        Some((byte_index, _)) => &s[..byte_index],
+        None => s,
+    }
+}
+
+/// Mask a secret for display, keeping only a short prefix of long values
+pub fn redact(secret: &str) -> String {
+    if secret.chars().count() > 8 {
+        format!("{}...redacted", truncate_chars(secret, 3))
+    } else {
+        "redacted".to_string()
+    }
+}
+
 /// Format duration in milliseconds to human readable string
 pub fn format_duration(duration_ms: u64) -> String {
     if duration_ms < 1000 {
@@ -34,7 +58,9 @@ pub fn format_duration(duration_ms: u64) -> String {
     }
 }
 
-/// Get current timestamp in milliseconds
+/// Get current wall-clock timestamp in milliseconds.
+///
+/// Not suitable for measuring durations; use [`std::time::Instant`] instead.
 pub fn current_time_millis() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -75,7 +101,102 @@ where
     safe_json_parse(data)
 }
 
+/// Detect the language of a text, mapped onto the supported `Language` variants.
+///
+/// Returns `None` when the input is too short to classify reliably or when the
+/// detected language is not one supported by Orama.
+pub fn detect_language(text: &str) -> Option<Language> {
+    use whatlang::Lang;
+
+    let significant_chars = text.chars().filter(|c| !c.is_whitespace()).count();
+    if significant_chars < MIN_LANGUAGE_DETECTION_CHARS {
+        return None;
+    }
+
+    let language = match whatlang::detect_lang(text)? {
+        Lang::Ara => Language::Arabic,
+        Lang::Bul => Language::Bulgarian,
+        Lang::Cmn => Language::Chinese,
+        Lang::Dan => Language::Danish,
+        Lang::Nld => Language::Dutch,
+        Lang::Deu => Language::German,
+        Lang::Ell => Language::Greek,
+        Lang::Eng => Language::English,
+        Lang::Est => Language::Estonian,
+        Lang::Spa => Language::Spanish,
+        Lang::Fin => Language::Finnish,
+        Lang::Fra => Language::French,
+        Lang::Hin => Language::Hindi,
+        Lang::Hun => Language::Hungarian,
+        Lang::Hye => Language::Armenian,
+        Lang::Ind => Language::Indonesian,
+        Lang::Ita => Language::Italian,
+        Lang::Jpn => Language::Japanese,
+        Lang::Kor => Language::Korean,
+        Lang::Lit => Language::Lithuanian,
+        Lang::Nep => Language::Nepali,
+        Lang::Nob => Language::Norwegian,
+        Lang::Por => Language::Portuguese,
+        Lang::Ron => Language::Romanian,
+        Lang::Rus => Language::Russian,
+        Lang::Slv => Language::Slovenian,
+        Lang::Srp => Language::Serbian,
+        Lang::Swe => Language::Swedish,
+        Lang::Tam => Language::Tamil,
+        Lang::Tur => Language::Turkish,
+        Lang::Ukr => Language::Ukrainian,
+        _ => return None,
+    };
+
+    Some(language)
+}
+
+/// Successes and failures of a batch of independent operations
+#[derive(Debug)]
+pub struct PartitionedResults<T> {
+    pub ok: Vec<T>,
+    pub errors: Vec<OramaError>,
+}
+
+impl<T> PartitionedResults<T> {
+    /// Split results into successes and failures, keeping the relative order
+    /// within each
+    pub fn from_results(results: Vec<crate::error::Result<T>>) -> Self {
+        results.into_iter().collect()
+    }
+
+    /// Whether every operation succeeded
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// All successes, or the first error if any operation failed
+    pub fn into_result_or_first_error(self) -> crate::error::Result<Vec<T>> {
+        match self.errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(self.ok),
+        }
+    }
+}
+
+impl<T> FromIterator<crate::error::Result<T>> for PartitionedResults<T> {
+    fn from_iter<I: IntoIterator<Item = crate::error::Result<T>>>(iter: I) -> Self {
+        let mut partitioned = Self {
+            ok: Vec::new(),
+            errors: Vec::new(),
+        };
+        for result in iter {
+            match result {
+                Ok(value) => partitioned.ok.push(value),
+                Err(error) => partitioned.errors.push(error),
+            }
+        }
+        partitioned
+    }
+}
+
 /// Throttle function execution
+#[derive(Debug)]
 pub struct Throttle {
     last_called: std::sync::Mutex<Option<Instant>>,
     limit: Duration,
@@ -109,6 +230,7 @@ impl Throttle {
 }
 
 /// Debounce function execution
+#[derive(Debug)]
 pub struct Debounce {
     timer: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
     delay: Duration,
@@ -143,3 +265,65 @@ impl Debounce {
         }));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_chars_keeps_multibyte_characters_whole() {
+        assert_eq!(truncate_chars("héllo👋🏽 wörld", 6), "héllo👋");
+        assert_eq!(truncate_chars("東京都の天気", 2), "東京");
+        assert_eq!(truncate_chars("🎉🎉🎉", 5), "🎉🎉🎉");
+        assert_eq!(truncate_chars("abc", 0), "");
+    }
+
+    #[test]
+    fn detects_clearly_identifiable_languages() {
+        assert_eq!(
+            detect_language("The quick brown fox jumps over the lazy dog near the river bank"),
+            Some(Language::English)
+        );
+        assert_eq!(
+            detect_language("El rápido zorro marrón salta sobre el perro perezoso junto al río"),
+            Some(Language::Spanish)
+        );
+        assert_eq!(
+            detect_language("素早い茶色の狐は川のそばで怠け者の犬を飛び越えます"),
+            Some(Language::Japanese)
+        );
+    }
+
+    #[test]
+    fn short_input_is_not_classified() {
+        assert_eq!(detect_language("hola"), None);
+        assert_eq!(detect_language("   "), None);
+    }
+
+    #[test]
+    fn partitioned_results_split_a_mixed_vector() {
+        let results: Vec<crate::error::Result<u32>> = vec![
+            Ok(1),
+            Err(OramaError::generic("first")),
+            Ok(2),
+            Err(OramaError::generic("second")),
+            Ok(3),
+        ];
+
+        let partitioned = PartitionedResults::from_results(results);
+
+        assert_eq!(partitioned.ok, [1, 2, 3]);
+        assert_eq!(partitioned.errors.len(), 2);
+        assert!(!partitioned.is_complete());
+        let error = partitioned.into_result_or_first_error().unwrap_err();
+        assert!(error.to_string().contains("first"));
+    }
+
+    #[test]
+    fn partitioned_results_without_errors_are_complete() {
+        let partitioned: PartitionedResults<u32> = vec![Ok(1), Ok(2)].into_iter().collect();
+
+        assert!(partitioned.is_complete());
+        assert_eq!(partitioned.into_result_or_first_error().unwrap(), [1, 2]);
+    }
+}
@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use oramacore_client::collection::{CollectionManagerConfig, Endpoints};
+use oramacore_client::{AnalyticsContext, CollectionManager};
+
+async fn manager(server: &mockito::Server, flush_interval: Duration) -> CollectionManager {
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()))
+        .with_analytics_flush_interval(flush_interval);
+    CollectionManager::new(config).await.unwrap()
+}
+
+fn events_mock(server: &mut mockito::Server) -> mockito::Mock {
+    server
+        .mock("POST", "/v1/collections/col/analytics/events")
+        .match_query(mockito::Matcher::Any)
+        .with_body("{}")
+}
+
+#[tokio::test]
+async fn events_are_batched_until_the_flush_interval() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = events_mock(&mut server)
+        .match_body(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::Regex(
+                r#""type":"search".*"type":"click".*"type":"conversion""#.into(),
+            ),
+            mockito::Matcher::Regex(r#""userID":"user-1""#.into()),
+            mockito::Matcher::Regex(r#""sessionID":"s-1""#.into()),
+        ]))
+        .expect(1)
+        .create_async()
+        .await;
+    let manager = manager(&server, Duration::from_millis(100)).await;
+    let context = AnalyticsContext::new("user-1").with_session_id("s-1");
+
+    let analytics = &manager.analytics;
+    analytics
+        .record_search_event(&context, "shoes", 12)
+        .await
+        .unwrap();
+    analytics.record_click(&context, "hit-3", 2).await.unwrap();
+    analytics
+        .record_conversion(&context, Some("hit-3".to_string()), Some(49.9))
+        .await
+        .unwrap();
+    assert_eq!(analytics.pending_events(), 3);
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+
+    mock.assert_async().await;
+    assert_eq!(analytics.pending_events(), 0);
+}
+
+#[tokio::test]
+async fn flush_sends_pending_events_immediately() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = events_mock(&mut server).expect(1).create_async().await;
+    let manager = manager(&server, Duration::from_secs(60)).await;
+    let context = AnalyticsContext::new("user-1");
+
+    manager
+        .analytics
+        .record_click(&context, "hit-1", 0)
+        .await
+        .unwrap();
+    manager.analytics.flush().await.unwrap();
+    manager.analytics.flush().await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(manager.analytics.pending_events(), 0);
+}
+
+#[tokio::test]
+async fn failed_flushes_keep_the_events() {
+    let mut server = mockito::Server::new_async().await;
+    events_mock(&mut server)
+        .with_status(500)
+        .create_async()
+        .await;
+    let manager = manager(&server, Duration::from_secs(60)).await;
+    let context = AnalyticsContext::new("user-1");
+
+    manager
+        .analytics
+        .record_click(&context, "hit-1", 0)
+        .await
+        .unwrap();
+    manager
+        .analytics
+        .record_click(&context, "hit-2", 1)
+        .await
+        .unwrap();
+
+    assert!(manager.analytics.flush().await.is_err());
+    assert_eq!(manager.analytics.pending_events(), 2);
+}
+
+#[tokio::test]
+async fn full_batches_are_sent_without_waiting() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = events_mock(&mut server).expect(1).create_async().await;
+    let manager = manager(&server, Duration::from_secs(60)).await;
+    let context = AnalyticsContext::new("user-1");
+
+    for position in 0..100 {
+        manager
+            .analytics
+            .record_click(&context, format!("hit-{position}"), position)
+            .await
+            .unwrap();
+    }
+
+    mock.assert_async().await;
+    assert_eq!(manager.analytics.pending_events(), 0);
+}
+
+#[tokio::test]
+async fn steady_traffic_does_not_postpone_the_flush() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = events_mock(&mut server)
+        .expect_at_least(1)
+        .create_async()
+        .await;
+    let manager = manager(&server, Duration::from_millis(200)).await;
+    let context = AnalyticsContext::new("user-1");
+
+    // An event every 50ms never leaves the 200ms interval quiet
+    for position in 0..12 {
+        manager
+            .analytics
+            .record_click(&context, format!("hit-{position}"), position)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    mock.assert_async().await;
+    assert!(manager.analytics.pending_events() < 12);
+}
+
+#[tokio::test]
+async fn failing_endpoint_does_not_fail_record_and_bounds_the_buffer() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = events_mock(&mut server)
+        .with_status(500)
+        .expect(1)
+        .create_async()
+        .await;
+    let manager = manager(&server, Duration::from_secs(60)).await;
+    let context = AnalyticsContext::new("user-1");
+
+    for position in 0..1050 {
+        manager
+            .analytics
+            .record_click(&context, format!("hit-{position}"), position)
+            .await
+            .unwrap();
+    }
+
+    // Only the first full batch is attempted right away; the rest wait for
+    // the timer and the oldest events are dropped past the bound
+    mock.assert_async().await;
+    assert_eq!(manager.analytics.pending_events(), 1000);
+}
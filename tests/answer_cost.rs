@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use oramacore_client::auth::{ApiKeyAuth, Auth, AuthConfig};
+use oramacore_client::client::OramaClient;
+use oramacore_client::stream_manager::{AnswerConfig, CreateAiSessionConfig};
+use oramacore_client::{Cost, LlmConfig, LlmProvider, OramaCoreStream};
+
+#[tokio::test]
+async fn non_streaming_answer_usage_counts_towards_the_estimated_cost() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/ai/answer")
+        .match_query(mockito::Matcher::Any)
+        .with_body(
+            r#"{"answer":"hi","usage":{"prompt_tokens":1000000,"completion_tokens":100000}}"#,
+        )
+        .create_async()
+        .await;
+
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url(server.url())),
+        Arc::new(reqwest::Client::new()),
+    );
+    let session = OramaCoreStream::with_config(
+        "col".to_string(),
+        OramaClient::new(auth).unwrap(),
+        CreateAiSessionConfig::new().with_llm_config(LlmConfig {
+            provider: LlmProvider::OpenAI,
+            model: "gpt-4o".to_string(),
+        }),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(session.answer(AnswerConfig::new("q")).await.unwrap(), "hi");
+
+    mock.assert_async().await;
+    assert_eq!(
+        session.estimated_cost().await,
+        Some(Cost {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            amount: 3.5,
+        })
+    );
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use oramacore_client::stream_manager::{AnswerConfig, StreamChunk};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+mod common;
+
+use common::{session, session_at};
+
+const DEADLINE: Duration = Duration::from_millis(300);
+const SERVER_DELAY: Duration = Duration::from_secs(3);
+
+#[tokio::test]
+async fn slow_answer_trips_the_deadline() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/v1/collections/col/ai/answer")
+        .match_query(mockito::Matcher::Any)
+        .with_chunked_body(|writer| {
+            std::thread::sleep(SERVER_DELAY);
+            writer.write_all(br#"{"answer":"late"}"#)
+        })
+        .create_async()
+        .await;
+    let session = session(&server).await;
+
+    let started = std::time::Instant::now();
+    let error = session
+        .answer(AnswerConfig::new("q").with_timeout(DEADLINE))
+        .await
+        .unwrap_err();
+
+    assert!(started.elapsed() < SERVER_DELAY);
+    assert!(error.to_string().contains("deadline of 300ms exceeded"));
+    let interaction = session.get_state().await.pop().unwrap();
+    assert!(interaction.error);
+    assert!(!interaction.loading);
+}
+
+#[tokio::test]
+async fn slow_stream_trips_the_deadline() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 8192];
+        let _ = socket.read(&mut buf).await;
+        let _ = socket
+            .write_all(
+                b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\n\
+                  data: {\"content\":\"partial\"}\n\n",
+            )
+            .await;
+        tokio::time::sleep(SERVER_DELAY).await;
+        let _ = socket.write_all(b"data: [DONE]\n\n").await;
+    });
+    let session = session_at(url).await;
+
+    let started = std::time::Instant::now();
+    let chunks: Vec<_> = session
+        .answer_stream(AnswerConfig::new("q").with_timeout(DEADLINE))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert!(started.elapsed() < SERVER_DELAY);
+    assert!(chunks
+        .iter()
+        .any(|chunk| matches!(chunk, Ok(StreamChunk::Content(c)) if c == "partial")));
+    let error = chunks.last().unwrap().as_ref().unwrap_err();
+    assert!(error.to_string().contains("deadline of 300ms exceeded"));
+    assert!(session.get_state().await.pop().unwrap().error);
+}
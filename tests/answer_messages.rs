@@ -0,0 +1,204 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use oramacore_client::auth::{ApiKeyAuth, Auth, AuthConfig};
+use oramacore_client::client::OramaClient;
+use oramacore_client::collection::{CollectionManagerConfig, Endpoints};
+use oramacore_client::stream_manager::{AnswerConfig, CreateAiSessionConfig, StreamChunk};
+use oramacore_client::{CollectionManager, Message, OramaCoreStream, Role};
+
+async fn session(server: &mockito::Server, history: Vec<Message>) -> OramaCoreStream {
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url(server.url())),
+        Arc::new(reqwest::Client::new()),
+    );
+    OramaCoreStream::with_config(
+        "col".to_string(),
+        OramaClient::new(auth).unwrap(),
+        CreateAiSessionConfig::new().with_initial_messages(history),
+    )
+    .await
+    .unwrap()
+}
+
+fn message(role: Role, content: &str) -> Message {
+    Message {
+        role,
+        content: content.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn provided_messages_replace_the_session_history() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/ai/answer")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "query": "and in blue?",
+            "messages": [
+                { "role": "user", "content": "red shoes" },
+                { "role": "assistant", "content": "Here are red shoes" }
+            ]
+        })))
+        .with_body(r#"{"answer":"hi"}"#)
+        .create_async()
+        .await;
+    let session = session(&server, vec![message(Role::User, "unrelated")]).await;
+
+    let config = AnswerConfig::new("and in blue?").with_messages(vec![
+        message(Role::User, "red shoes"),
+        message(Role::Assistant, "Here are red shoes"),
+    ]);
+    session.answer(config).await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn session_history_is_sent_without_provided_messages() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/ai/answer")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "messages": [{ "role": "user", "content": "earlier" }]
+        })))
+        .with_body(r#"{"answer":"hi"}"#)
+        .create_async()
+        .await;
+    let session = session(&server, vec![message(Role::User, "earlier")]).await;
+
+    session.answer(AnswerConfig::new("q")).await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn messages_stay_empty_when_history_tracking_is_off() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/v1/collections/col/ai/answer")
+        .match_query(mockito::Matcher::Any)
+        .with_body(r#"{"answer":"hi"}"#)
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/v1/collections/col/ai/answer/stream")
+        .match_query(mockito::Matcher::Any)
+        .with_header("content-type", "text/event-stream")
+        .with_body("data: {\"content\":\"hi\"}\n\ndata: [DONE]\n\n")
+        .create_async()
+        .await;
+    let session = session(&server, Vec::new()).await;
+    session.set_track_history(false);
+
+    assert_eq!(session.answer(AnswerConfig::new("q")).await.unwrap(), "hi");
+    let chunks: Vec<_> = session
+        .answer_stream(AnswerConfig::new("q"))
+        .await
+        .unwrap()
+        .take_while(|chunk| futures::future::ready(!matches!(chunk, Ok(StreamChunk::Done))))
+        .collect()
+        .await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert!(chunks.iter().all(Result::is_ok));
+    assert!(session.get_messages().await.is_empty());
+    assert_eq!(session.interaction_count().await, 2);
+}
+
+#[tokio::test]
+async fn system_prompt_ids_reach_the_request_in_order() {
+    let mut server = mockito::Server::new_async().await;
+    let session_prompts = server
+        .mock("POST", "/v1/collections/col/ai/answer")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "query": "first",
+            "system_prompt_ids": ["persona", "tone"]
+        })))
+        .with_body(r#"{"answer":"hi"}"#)
+        .create_async()
+        .await;
+    let request_prompts = server
+        .mock("POST", "/v1/collections/col/ai/answer")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "query": "second",
+            "system_prompt_ids": ["legal"]
+        })))
+        .with_body(r#"{"answer":"hi"}"#)
+        .create_async()
+        .await;
+    let session = session(&server, Vec::new())
+        .await
+        .with_system_prompt_id("persona")
+        .with_system_prompt_id("tone");
+
+    session.answer(AnswerConfig::new("first")).await.unwrap();
+    session
+        .answer(AnswerConfig::new("second").with_system_prompt_id("legal"))
+        .await
+        .unwrap();
+
+    session_prompts.assert_async().await;
+    request_prompts.assert_async().await;
+}
+
+#[tokio::test]
+async fn one_shot_answers_carry_no_history_between_calls() {
+    let mut server = mockito::Server::new_async().await;
+    let without_history = |request: &mockito::Request| {
+        let body: serde_json::Value = serde_json::from_slice(request.body().unwrap()).unwrap();
+        body.get("messages").is_none()
+    };
+    let answer = server
+        .mock("POST", "/v1/collections/col/ai/answer")
+        .match_query(mockito::Matcher::Any)
+        .match_request(without_history)
+        .with_body(r#"{"answer":"hi"}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let stream = server
+        .mock("POST", "/v1/collections/col/ai/answer/stream")
+        .match_query(mockito::Matcher::Any)
+        .match_request(without_history)
+        .with_header("content-type", "text/event-stream")
+        .with_body("data: {\"content\":\"hi\"}\n\ndata: [DONE]\n\n")
+        .create_async()
+        .await;
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()));
+    let manager = CollectionManager::new(config).await.unwrap();
+
+    assert_eq!(
+        manager
+            .ai
+            .answer_once(AnswerConfig::new("first"))
+            .await
+            .unwrap(),
+        "hi"
+    );
+    assert_eq!(
+        manager
+            .ai
+            .answer_once(AnswerConfig::new("second"))
+            .await
+            .unwrap(),
+        "hi"
+    );
+    let chunks: Vec<_> = manager
+        .ai
+        .answer_once_stream(AnswerConfig::new("third"))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    answer.assert_async().await;
+    stream.assert_async().await;
+    assert!(chunks.iter().all(Result::is_ok));
+}
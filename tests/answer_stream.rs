@@ -0,0 +1,334 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use oramacore_client::auth::{ApiKeyAuth, Auth, AuthConfig};
+use oramacore_client::client::OramaClient;
+use oramacore_client::stream_manager::{
+    AnswerConfig, CreateAiSessionConfig, Interaction, StreamChunk, StreamConfig,
+};
+use oramacore_client::{OramaCoreStream, OramaError, Result};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+mod common;
+
+use common::{session, session_at};
+
+/// Mock the streaming answer endpoint with the given SSE `data` payloads
+async fn mock_stream(server: &mut mockito::Server, frames: &[&str]) -> mockito::Mock {
+    let body: String = frames
+        .iter()
+        .map(|data| format!("data: {data}\n\n"))
+        .collect();
+    server
+        .mock("POST", "/v1/collections/col/ai/answer/stream")
+        .match_query(mockito::Matcher::Any)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await
+}
+
+/// The last interaction, once the state updates spawned by the stream ran
+async fn settled_interaction(session: &OramaCoreStream) -> Interaction {
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    session.get_state().await.pop().unwrap()
+}
+
+/// Collect the chunks of a streaming answer up to and including `Done`
+async fn collect(session: &OramaCoreStream, config: AnswerConfig) -> Vec<Result<StreamChunk>> {
+    let mut stream = session.answer_stream(config).await.unwrap();
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let done = matches!(chunk, Ok(StreamChunk::Done));
+        chunks.push(chunk);
+        if done {
+            break;
+        }
+    }
+    chunks
+}
+
+#[tokio::test]
+async fn terminal_frame_is_reported_before_done() {
+    let mut server = mockito::Server::new_async().await;
+    mock_stream(
+        &mut server,
+        &[
+            r#"{"content":"hi"}"#,
+            r#"{"finish_reason":"stop","usage":{"total_tokens":7},"message_id":"m1"}"#,
+            "[DONE]",
+        ],
+    )
+    .await;
+    let session = session(&server).await;
+
+    let chunks = collect(&session, AnswerConfig::new("q")).await;
+    let chunks: Vec<_> = chunks.into_iter().map(Result::unwrap).collect();
+
+    assert_eq!(
+        chunks[chunks.len() - 2..],
+        [
+            StreamChunk::Completed {
+                finish_reason: Some("stop".to_string()),
+                usage: Some(serde_json::json!({ "total_tokens": 7 })),
+                message_id: Some("m1".to_string()),
+            },
+            StreamChunk::Done,
+        ]
+    );
+    let interaction = settled_interaction(&session).await;
+    assert_eq!(interaction.finish_reason.as_deref(), Some("stop"));
+    assert_eq!(interaction.message_id.as_deref(), Some("m1"));
+    assert!(!interaction.loading);
+}
+
+#[tokio::test]
+async fn bare_done_sentinel_completes_the_stream() {
+    let mut server = mockito::Server::new_async().await;
+    mock_stream(&mut server, &[r#"{"content":"hi"}"#, "[DONE]"]).await;
+    let session = session(&server).await;
+
+    let chunks = collect(&session, AnswerConfig::new("q")).await;
+
+    assert!(matches!(chunks.last(), Some(Ok(StreamChunk::Done))));
+    assert!(!chunks
+        .iter()
+        .any(|chunk| matches!(chunk, Ok(StreamChunk::Completed { .. }))));
+    let interaction = settled_interaction(&session).await;
+    assert_eq!(interaction.response, "hi");
+    assert!(!interaction.loading);
+    assert!(!interaction.error);
+}
+
+#[tokio::test]
+async fn accumulated_text_matches_the_concatenated_deltas() {
+    let mut server = mockito::Server::new_async().await;
+    mock_stream(
+        &mut server,
+        &[
+            r#"{"content":"Hel"}"#,
+            r#"{"step":"answer"}"#,
+            r#"{"content":"lo, "}"#,
+            r#"{"content":"world"}"#,
+            "[DONE]",
+        ],
+    )
+    .await;
+    let session = session(&server).await;
+
+    let deltas: Vec<_> = session
+        .answer_stream_accumulated(AnswerConfig::new("q"))
+        .await
+        .unwrap()
+        .take_while(|delta| futures::future::ready(delta.is_ok()))
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let concatenated: String = deltas.iter().map(|d| d.delta.as_str()).collect();
+    assert_eq!(concatenated, "Hello, world");
+    assert_eq!(deltas.last().unwrap().accumulated, concatenated);
+    assert_eq!(deltas[0].accumulated, "Hel");
+}
+
+#[tokio::test]
+async fn heartbeats_are_not_surfaced_as_raw_data() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/v1/collections/col/ai/answer/stream")
+        .with_header("content-type", "text/event-stream")
+        .with_body(concat!(
+            ": keep-alive comment\n\n",
+            "data: {\"content\":\"Hel\"}\n\n",
+            "event: ping\ndata: {}\n\n",
+            "data: heartbeat\n\n",
+            "data: {\"content\":\"lo\"}\n\n",
+            "data: [DONE]\n\n",
+        ))
+        .create_async()
+        .await;
+    let session = session(&server).await;
+
+    let chunks: Vec<_> = collect(&session, AnswerConfig::new("q"))
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert!(!chunks
+        .iter()
+        .any(|chunk| matches!(chunk, StreamChunk::RawData(_))));
+    assert_eq!(
+        chunks
+            .iter()
+            .filter(|chunk| **chunk == StreamChunk::Heartbeat)
+            .count(),
+        2
+    );
+    let content: String = chunks
+        .iter()
+        .filter_map(|chunk| match chunk {
+            StreamChunk::Content(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(content, "Hello");
+    assert_eq!(settled_interaction(&session).await.response, "Hello");
+}
+
+#[tokio::test]
+async fn raw_frames_pass_through_untouched() {
+    let frames = [
+        r#"{"content":"hi"}"#,
+        r#"{ "step" : "answer", "extra": [1, 2] }"#,
+        "not json at all",
+        "[DONE]",
+    ];
+    let mut server = mockito::Server::new_async().await;
+    mock_stream(&mut server, &frames).await;
+    let session = session(&server).await;
+
+    let raw: Vec<String> = session
+        .answer_stream_raw(AnswerConfig::new("q"))
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(raw, frames);
+    assert_eq!(session.interaction_count().await, 0);
+    assert!(session.get_messages().await.is_empty());
+}
+
+#[tokio::test]
+async fn slow_consumer_bounds_how_far_the_producer_reads_ahead() {
+    let mut server = mockito::Server::new_async().await;
+    let mut frames = vec![r#"{"content":"x"}"#; 50];
+    frames.push("[DONE]");
+    mock_stream(&mut server, &frames).await;
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url(server.url())),
+        Arc::new(reqwest::Client::new()),
+    );
+    let session = OramaCoreStream::with_stream_config(
+        "col".to_string(),
+        OramaClient::new(auth).unwrap(),
+        CreateAiSessionConfig::new(),
+        StreamConfig {
+            channel_capacity: 2,
+            ..StreamConfig::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut stream = session.answer_stream(AnswerConfig::new("q")).await.unwrap();
+    while !matches!(stream.next().await, Some(Ok(StreamChunk::Content(_)))) {}
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // One chunk consumed, two buffered, one held by the blocked producer
+    let produced = session.last_interaction().await.unwrap().response.len();
+    assert!(produced <= 4, "producer ran {produced} chunks ahead");
+
+    let rest: Vec<_> = stream.collect().await;
+    assert!(matches!(rest.last(), Some(Ok(StreamChunk::Done))));
+    assert_eq!(session.last_interaction().await.unwrap().response.len(), 50);
+}
+
+#[tokio::test]
+async fn connection_closed_without_done_is_incomplete() {
+    let mut server = mockito::Server::new_async().await;
+    mock_stream(&mut server, &[r#"{"content":"Hel"}"#, r#"{"content":"lo"#]).await;
+    let session = session(&server).await;
+
+    let chunks = collect(&session, AnswerConfig::new("q")).await;
+
+    assert!(matches!(
+        chunks.last(),
+        Some(Ok(StreamChunk::Incomplete { .. }))
+    ));
+    assert!(!chunks
+        .iter()
+        .any(|chunk| matches!(chunk, Ok(StreamChunk::Done))));
+    let interaction = session.last_interaction().await.unwrap();
+    assert!(!interaction.loading);
+    assert!(interaction.error);
+    assert!(interaction.error_message.is_some());
+}
+
+#[tokio::test]
+async fn server_errors_end_the_stream_without_reconnecting() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/ai/answer/stream")
+        .match_query(mockito::Matcher::Any)
+        .with_status(500)
+        .expect(1)
+        .create_async()
+        .await;
+    let session = session(&server).await;
+
+    let chunks: Vec<_> = tokio::time::timeout(
+        Duration::from_secs(5),
+        session
+            .answer_stream(AnswerConfig::new("q"))
+            .await
+            .unwrap()
+            .collect(),
+    )
+    .await
+    .expect("the stream reconnected instead of ending");
+
+    mock.assert_async().await;
+    assert_eq!(chunks.len(), 1);
+    assert!(matches!(
+        chunks[0],
+        Err(OramaError::Api { status: 500, .. })
+    ));
+    assert!(settled_interaction(&session).await.error);
+}
+
+#[tokio::test]
+async fn dropped_connections_end_the_stream_without_reconnecting() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+
+    // Promise a longer body than is sent, then drop the connection mid-answer
+    let counter = connections.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+            let mut buf = [0u8; 8192];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\
+                      content-length: 1000\r\n\r\ndata: {\"content\":\"Hel\"}\n\n",
+                )
+                .await;
+        }
+    });
+    let session = session_at(url).await;
+
+    let chunks: Vec<_> = tokio::time::timeout(
+        Duration::from_secs(5),
+        session
+            .answer_stream(AnswerConfig::new("q"))
+            .await
+            .unwrap()
+            .collect(),
+    )
+    .await
+    .expect("the stream reconnected instead of ending");
+
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+    assert_eq!(chunks.iter().filter(|chunk| chunk.is_err()).count(), 1);
+    assert!(chunks.last().unwrap().is_err());
+    assert!(settled_interaction(&session).await.error);
+}
@@ -0,0 +1,371 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use oramacore_client::auth::{ApiKeyAuth, Auth, AuthConfig, Target};
+use oramacore_client::client::{
+    ApiKeyPosition, ClientOptions, ClientRequest, OramaClient, ResponseMeta,
+};
+use oramacore_client::OramaError;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+fn client(server: &mockito::Server) -> OramaClient {
+    client_with_options(server, ClientOptions::default())
+}
+
+fn client_with_options(server: &mockito::Server, options: ClientOptions) -> OramaClient {
+    let auth = Auth::new(
+        AuthConfig::ApiKey(
+            ApiKeyAuth::new("key")
+                .with_reader_url(server.url())
+                .with_writer_url(server.url()),
+        ),
+        Arc::new(reqwest::Client::new()),
+    );
+    OramaClient::with_options(auth, options).unwrap()
+}
+
+fn get(path: &str) -> ClientRequest<()> {
+    ClientRequest::get(
+        path.to_string(),
+        Target::Reader,
+        ApiKeyPosition::QueryParams,
+    )
+}
+
+#[tokio::test]
+async fn response_meta_is_read_from_headers() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/v1/ping")
+        .match_query(mockito::Matcher::Any)
+        .with_header("x-request-id", "req-42")
+        .with_header("x-ratelimit-remaining", "17")
+        .with_header("x-ratelimit-reset", "1700000000")
+        .with_body(r#"{"ok":true}"#)
+        .create_async()
+        .await;
+
+    let (body, meta): (serde_json::Value, ResponseMeta) = client(&server)
+        .request_with_meta(get("/v1/ping"))
+        .await
+        .unwrap();
+
+    assert_eq!(body["ok"], true);
+    assert_eq!(
+        meta,
+        ResponseMeta {
+            request_id: Some("req-42".to_string()),
+            rate_limit_remaining: Some(17),
+            rate_limit_reset: Some(1_700_000_000),
+        }
+    );
+}
+
+#[tokio::test]
+async fn bodies_over_the_size_limit_are_rejected() {
+    let mut server = mockito::Server::new_async().await;
+    let body = format!(r#"{{"data":"{}"}}"#, "x".repeat(100));
+    server
+        .mock("GET", "/v1/sized")
+        .match_query(mockito::Matcher::Any)
+        .with_body(&body)
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/v1/chunked")
+        .match_query(mockito::Matcher::Any)
+        .with_chunked_body(move |writer| writer.write_all(body.as_bytes()))
+        .create_async()
+        .await;
+    let client = client_with_options(&server, ClientOptions::new().with_max_response_bytes(64));
+
+    for path in ["/v1/sized", "/v1/chunked"] {
+        let error = client
+            .request::<_, serde_json::Value>(get(path))
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("maximum allowed size of 64 bytes"));
+    }
+}
+
+#[tokio::test]
+async fn malformed_json_is_only_repaired_when_enabled() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/v1/trailing")
+        .match_query(mockito::Matcher::Any)
+        .with_body(r#"{"count": 3,}"#)
+        .create_async()
+        .await;
+
+    let strict = client(&server)
+        .request::<_, serde_json::Value>(get("/v1/trailing"))
+        .await;
+    let repaired_by_client =
+        client_with_options(&server, ClientOptions::new().with_repair_responses(true))
+            .request::<_, serde_json::Value>(get("/v1/trailing"))
+            .await
+            .unwrap();
+    let repaired_by_request = client(&server)
+        .request::<_, serde_json::Value>(get("/v1/trailing").with_json_repair(true))
+        .await
+        .unwrap();
+
+    assert!(strict.is_err());
+    assert_eq!(repaired_by_client["count"], 3);
+    assert_eq!(repaired_by_request["count"], 3);
+}
+
+#[tokio::test]
+async fn user_agent_suffix_is_sent() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v1/ping")
+        .match_query(mockito::Matcher::Any)
+        .match_header(
+            "user-agent",
+            format!(
+                "oramacore-client-rust/{} myapp/2.0",
+                env!("CARGO_PKG_VERSION")
+            )
+            .as_str(),
+        )
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    client_with_options(
+        &server,
+        ClientOptions::new().with_user_agent_suffix("myapp/2.0"),
+    )
+    .request::<_, serde_json::Value>(get("/v1/ping"))
+    .await
+    .unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn client_builds_with_custom_pool_settings() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v1/ping")
+        .match_query(mockito::Matcher::Any)
+        .with_body("{}")
+        .expect(2)
+        .create_async()
+        .await;
+    let options = ClientOptions::new()
+        .with_pool_max_idle_per_host(2)
+        .with_pool_idle_timeout(Duration::from_secs(5));
+    let client = client_with_options(&server, options);
+
+    for _ in 0..2 {
+        client
+            .request::<_, serde_json::Value>(get("/v1/ping"))
+            .await
+            .unwrap();
+    }
+
+    mock.assert_async().await;
+}
+
+#[test]
+fn client_builds_with_http2_prior_knowledge() {
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url("http://localhost")),
+        Arc::new(reqwest::Client::new()),
+    );
+
+    let options = ClientOptions::new().with_http2_prior_knowledge(true);
+
+    assert!(OramaClient::with_options(auth, options).is_ok());
+}
+
+#[test]
+fn client_builds_with_a_custom_root_certificate() {
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url("https://localhost")),
+        Arc::new(reqwest::Client::new()),
+    );
+    let certificate =
+        reqwest::Certificate::from_pem(include_bytes!("fixtures/root_ca.pem")).unwrap();
+
+    let options = ClientOptions::new().add_root_certificate(certificate);
+
+    assert_eq!(options.root_certificates.len(), 1);
+    assert!(OramaClient::with_options(auth, options).is_ok());
+}
+
+#[test]
+fn client_builds_with_invalid_certs_accepted() {
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url("https://localhost")),
+        Arc::new(reqwest::Client::new()),
+    );
+
+    let options = ClientOptions::new().danger_accept_invalid_certs(true);
+
+    assert!(options.accept_invalid_certs);
+    assert!(OramaClient::with_options(auth, options).is_ok());
+}
+
+#[tokio::test]
+async fn call_builder_sends_a_custom_get_with_params() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v1/collections/col/stats")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("api-key".into(), "key".into()),
+            mockito::Matcher::UrlEncoded("verbose".into(), "true".into()),
+        ]))
+        .match_header("x-trace", "abc")
+        .with_body(r#"{"document_count":3}"#)
+        .create_async()
+        .await;
+
+    let stats: serde_json::Value = client(&server)
+        .call(reqwest::Method::GET, "/v1/collections/col/stats")
+        .target(Target::Reader)
+        .api_key_position(ApiKeyPosition::QueryParams)
+        .param("verbose", "true")
+        .header("x-trace", "abc")
+        .send()
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(stats["document_count"], 3);
+}
+
+#[tokio::test]
+async fn no_content_responses_decode_into_unit_like_types() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/v1/empty")
+        .match_query(mockito::Matcher::Any)
+        .with_status(204)
+        .create_async()
+        .await;
+    let client = client(&server);
+
+    client.request::<_, ()>(get("/v1/empty")).await.unwrap();
+    let optional: Option<serde_json::Value> = client.request(get("/v1/empty")).await.unwrap();
+    assert_eq!(optional, None);
+
+    let error = client
+        .request::<_, Vec<String>>(get("/v1/empty"))
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("Empty response body"), "{error}");
+}
+
+/// Serve an empty JSON object to every request after `delay`
+async fn serve_after(delay: Duration) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let mut buf = [0u8; 8192];
+                let _ = socket.read(&mut buf).await;
+                tokio::time::sleep(delay).await;
+                let _ = socket
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                          content-length: 2\r\nconnection: close\r\n\r\n{}",
+                    )
+                    .await;
+            });
+        }
+    });
+
+    url
+}
+
+#[tokio::test]
+async fn writer_timeout_applies_to_writes_only() {
+    let url = serve_after(Duration::from_millis(300)).await;
+    let auth = Auth::new(
+        AuthConfig::ApiKey(
+            ApiKeyAuth::new("key")
+                .with_reader_url(url.clone())
+                .with_writer_url(url),
+        ),
+        Arc::new(reqwest::Client::new()),
+    );
+    let options = ClientOptions::new().with_target_options(
+        Target::Writer,
+        ClientOptions::new().with_timeout(Duration::from_millis(50)),
+    );
+    let client = OramaClient::with_options(auth, options).unwrap();
+
+    let read: serde_json::Value = client.request(get("/read")).await.unwrap();
+    assert_eq!(read, serde_json::json!({}));
+
+    let write = ClientRequest::post(
+        "/write".to_string(),
+        Target::Writer,
+        ApiKeyPosition::Header,
+        serde_json::json!({}),
+    );
+    let error = client
+        .request::<_, serde_json::Value>(write)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, OramaError::Http(e) if e.is_timeout()));
+}
+
+#[tokio::test]
+async fn stream_response_yields_a_chunked_body() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/export")
+        .match_query(mockito::Matcher::UrlEncoded("api-key".into(), "key".into()))
+        .with_chunked_body(|writer| {
+            for line in ["first\n", "second\n", "third\n"] {
+                writer.write_all(line.as_bytes())?;
+                writer.flush()?;
+            }
+            Ok(())
+        })
+        .create_async()
+        .await;
+
+    let stream = client(&server)
+        .stream_response(get("/export"))
+        .await
+        .unwrap();
+    let body: Vec<u8> = stream.map(|chunk| chunk.unwrap().to_vec()).concat().await;
+
+    mock.assert_async().await;
+    assert_eq!(body, b"first\nsecond\nthird\n");
+}
+
+#[tokio::test]
+async fn stream_response_maps_error_statuses() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/export")
+        .match_query(mockito::Matcher::Any)
+        .with_status(404)
+        .with_body(r#"{"message":"not found"}"#)
+        .create_async()
+        .await;
+
+    let error = match client(&server).stream_response(get("/export")).await {
+        Ok(_) => panic!("expected an error status to fail"),
+        Err(error) => error,
+    };
+
+    assert!(
+        matches!(error, OramaError::Api { status: 404, .. }),
+        "{error}"
+    );
+}
@@ -0,0 +1,76 @@
+use futures::StreamExt;
+use oramacore_client::cloud::{CloudSearchParams, ProjectManagerConfig};
+use oramacore_client::collection::Endpoints;
+use oramacore_client::OramaCloud;
+
+async fn cloud(server: &mockito::Server) -> OramaCloud {
+    let config = ProjectManagerConfig::new("project", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()));
+    OramaCloud::new(config).await.unwrap()
+}
+
+#[tokio::test]
+async fn search_paginated_maps_datasources_to_indexes() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("POST", "/v1/collections/project/search")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "term": "shoes",
+            "indexes": ["docs", "blog"],
+        })))
+        .with_body(
+            r#"{"count":2,"hits":[{"id":"1","score":1.0,"document":{}}],"next_cursor":"page-2"}"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+    let second = server
+        .mock("POST", "/v1/collections/project/search")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "indexes": ["docs", "blog"],
+            "cursor": "page-2",
+        })))
+        .with_body(r#"{"count":2,"hits":[{"id":"2","score":0.5,"document":{}}]}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let cloud = cloud(&server).await;
+    let params = CloudSearchParams::new("shoes", vec!["docs".into(), "blog".into()]);
+    let pages: Vec<_> = cloud
+        .search_paginated::<serde_json::Value>(&params)
+        .collect()
+        .await;
+
+    first.assert_async().await;
+    second.assert_async().await;
+    let ids: Vec<String> = pages
+        .into_iter()
+        .flat_map(|page| page.unwrap().hits)
+        .map(|hit| hit.id)
+        .collect();
+    assert_eq!(ids, ["1", "2"]);
+}
+
+#[tokio::test]
+async fn count_maps_datasources_to_indexes() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/project/search")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "indexes": ["docs"],
+            "limit": 0,
+        })))
+        .with_body(r#"{"count":42,"hits":[]}"#)
+        .create_async()
+        .await;
+
+    let params = CloudSearchParams::new("shoes", vec!["docs".into()]);
+    let count = cloud(&server).await.count(&params).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(count, 42);
+}
@@ -0,0 +1,76 @@
+use oramacore_client::codec::DocumentCodec;
+use oramacore_client::collection::{CollectionManagerConfig, Endpoints};
+use oramacore_client::CollectionManager;
+use serde::Serialize;
+
+/// Renames top-level snake_case keys to camelCase
+#[derive(Debug)]
+struct CamelCaseCodec;
+
+impl DocumentCodec for CamelCaseCodec {
+    fn transform(
+        &self,
+        document: serde_json::Value,
+    ) -> oramacore_client::Result<serde_json::Value> {
+        let serde_json::Value::Object(map) = document else {
+            return Ok(document);
+        };
+
+        let camel_case = |key: &str| {
+            let mut parts = key.split('_');
+            let mut out = parts.next().unwrap_or_default().to_string();
+            for part in parts {
+                let mut chars = part.chars();
+                if let Some(first) = chars.next() {
+                    out.extend(first.to_uppercase());
+                    out.push_str(chars.as_str());
+                }
+            }
+            out
+        };
+
+        Ok(map
+            .into_iter()
+            .map(|(key, value)| (camel_case(&key), value))
+            .collect())
+    }
+}
+
+#[derive(Serialize)]
+struct Product {
+    id: String,
+    product_name: String,
+    unit_price_cents: u32,
+}
+
+#[tokio::test]
+async fn inserted_documents_go_through_the_codec() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/insert")
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "documents": [{ "id": "1", "productName": "Sneaker", "unitPriceCents": 4999 }]
+        })))
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()));
+    let index = CollectionManager::new(config)
+        .await
+        .unwrap()
+        .index
+        .set("idx".to_string())
+        .with_codec(CamelCaseCodec);
+    index
+        .insert_documents(vec![Product {
+            id: "1".to_string(),
+            product_name: "Sneaker".to_string(),
+            unit_price_cents: 4999,
+        }])
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+}
@@ -0,0 +1,32 @@
+//! Fixtures shared by the integration tests
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use oramacore_client::auth::{ApiKeyAuth, Auth, AuthConfig};
+use oramacore_client::client::OramaClient;
+use oramacore_client::collection::{CollectionManagerConfig, Endpoints};
+use oramacore_client::{CollectionManager, OramaCoreStream};
+
+/// A manager for collection `col` with every endpoint pointing at `server`
+pub async fn manager(server: &mockito::Server) -> CollectionManager {
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()));
+    CollectionManager::new(config).await.unwrap()
+}
+
+/// An answer session on collection `col` reading from `server`
+pub async fn session(server: &mockito::Server) -> OramaCoreStream {
+    session_at(server.url()).await
+}
+
+/// An answer session on collection `col` reading from `url`
+pub async fn session_at(url: String) -> OramaCoreStream {
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url(url)),
+        Arc::new(reqwest::Client::new()),
+    );
+    OramaCoreStream::new("col".to_string(), OramaClient::new(auth).unwrap())
+        .await
+        .unwrap()
+}
@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use oramacore_client::auth::{ApiKeyAuth, Auth, AuthConfig, Target};
+use oramacore_client::client::{ApiKeyPosition, ClientOptions, ClientRequest, OramaClient};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Serve slow JSON responses, recording the highest number of requests
+/// handled at the same time
+async fn serve_slowly(peak: Arc<AtomicUsize>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let in_flight = Arc::new(AtomicUsize::new(0));
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let (in_flight, peak) = (in_flight.clone(), peak.clone());
+            tokio::spawn(async move {
+                let mut buf = [0u8; 8192];
+                let _ = socket.read(&mut buf).await;
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(current, Ordering::SeqCst);
+
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                let _ = socket
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                          content-length: 2\r\nconnection: close\r\n\r\n{}",
+                    )
+                    .await;
+            });
+        }
+    });
+
+    url
+}
+
+#[tokio::test]
+async fn max_concurrency_caps_requests_in_flight() {
+    let peak = Arc::new(AtomicUsize::new(0));
+    let url = serve_slowly(peak.clone()).await;
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url(url)),
+        Arc::new(reqwest::Client::new()),
+    );
+    let client =
+        OramaClient::with_options(auth, ClientOptions::new().with_max_concurrency(2)).unwrap();
+
+    let requests = (0..6).map(|_| {
+        client.request::<_, serde_json::Value>(ClientRequest::<()>::get(
+            "/v1/ping".to_string(),
+            Target::Reader,
+            ApiKeyPosition::QueryParams,
+        ))
+    });
+    for result in futures::future::join_all(requests).await {
+        result.unwrap();
+    }
+
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+}
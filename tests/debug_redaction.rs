@@ -0,0 +1,43 @@
+use oramacore_client::auth::{ApiKeyAuth, AuthConfig, AuthRef, JwtAuth};
+use oramacore_client::cloud::ProjectManagerConfig;
+use oramacore_client::collection::CollectionManagerConfig;
+use oramacore_client::manager::OramaCoreManagerConfig;
+
+const SECRET: &str = "sk_live_0123456789abcdef";
+
+fn assert_redacted(debug: String) {
+    assert!(!debug.contains(SECRET), "secret leaked: {debug}");
+    assert!(debug.contains("redacted"), "no redaction marker: {debug}");
+}
+
+#[test]
+fn auth_configs_redact_keys() {
+    assert_redacted(format!("{:?}", ApiKeyAuth::new(SECRET)));
+    assert_redacted(format!(
+        "{:?}",
+        AuthConfig::Jwt(JwtAuth::new("http://localhost/jwt", "col", SECRET))
+    ));
+    assert_redacted(format!(
+        "{:?}",
+        AuthRef {
+            bearer: SECRET.to_string(),
+            base_url: "http://localhost".to_string(),
+        }
+    ));
+}
+
+#[test]
+fn manager_configs_redact_keys() {
+    assert_redacted(format!("{:?}", CollectionManagerConfig::new("col", SECRET)));
+    assert_redacted(format!(
+        "{:?}",
+        ProjectManagerConfig::new("project", SECRET)
+    ));
+    assert_redacted(format!(
+        "{:?}",
+        OramaCoreManagerConfig {
+            url: "http://localhost".to_string(),
+            master_api_key: SECRET.to_string(),
+        }
+    ));
+}
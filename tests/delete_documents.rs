@@ -0,0 +1,76 @@
+use oramacore_client::collection::{CollectionManagerConfig, Endpoints};
+use oramacore_client::{CollectionManager, OramaError};
+
+#[tokio::test]
+async fn delete_across_indexes_keeps_the_partial_report_on_failure() {
+    let mut server = mockito::Server::new_async().await;
+    let succeeded = server
+        .mock("POST", "/v1/collections/col/indexes/a/documents/delete")
+        .with_body(r#"{"deleted":1,"deleted_ids":["d1"]}"#)
+        .create_async()
+        .await;
+    let failed = server
+        .mock("POST", "/v1/collections/col/indexes/b/documents/delete")
+        .with_status(500)
+        .with_body("boom")
+        .create_async()
+        .await;
+
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()));
+    let manager = CollectionManager::new(config).await.unwrap();
+
+    let report = manager
+        .delete_documents_in_indexes(
+            vec!["d1".to_string(), "d2".to_string()],
+            vec!["a".to_string(), "b".to_string()],
+        )
+        .await
+        .unwrap();
+
+    succeeded.assert_async().await;
+    failed.assert_async().await;
+    assert!(!report.is_complete());
+    assert_eq!(report.deleted, 1);
+    assert_eq!(report.deleted_ids, ["d1"]);
+    assert_eq!(report.by_index["a"], ["d1"]);
+    assert!(!report.by_index.contains_key("b"));
+    assert!(matches!(
+        report.failed["b"],
+        OramaError::Api { status: 500, .. }
+    ));
+}
+
+#[tokio::test]
+async fn delete_across_indexes_fails_when_every_index_fails() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock(
+            "POST",
+            mockito::Matcher::Regex(r"^/v1/collections/col/indexes/\w+/documents/delete$".into()),
+        )
+        .with_status(500)
+        .with_body("boom")
+        .expect(3)
+        .create_async()
+        .await;
+
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()));
+    let manager = CollectionManager::new(config).await.unwrap();
+    let ids = vec!["d1".to_string()];
+
+    let single = manager
+        .delete_documents_in_indexes(ids.clone(), vec!["a".to_string()])
+        .await
+        .unwrap_err();
+    let several = manager
+        .delete_documents_in_indexes(ids, vec!["b".to_string(), "c".to_string()])
+        .await
+        .unwrap_err();
+
+    assert!(matches!(single, OramaError::Api { status: 500, .. }));
+    let message = several.to_string();
+    assert!(message.contains("Delete failed on every index"));
+    assert!(message.contains("b: ") && message.contains("c: "));
+}
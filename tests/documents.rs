@@ -0,0 +1,286 @@
+use futures::StreamExt;
+use oramacore_client::collection::{
+    BatchInsertReport, CollectionManagerConfig, DocumentPatch, Endpoints, Index,
+};
+use oramacore_client::filter::Filter;
+use oramacore_client::CollectionManager;
+
+mod common;
+
+use common::manager;
+
+async fn index(server: &mockito::Server) -> Index {
+    manager(server).await.index.set("idx".to_string())
+}
+
+#[tokio::test]
+async fn delete_by_filter_sends_the_filter() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock(
+            "POST",
+            "/v1/collections/col/indexes/idx/documents/delete_by_filter",
+        )
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "filter": { "status": "archived", "year": { "lt": 2020.0 } }
+        })))
+        .with_body(r#"{"deleted":2}"#)
+        .create_async()
+        .await;
+
+    let filter = Filter::new().eq("status", "archived").lt("year", 2020.0);
+    let report = index(&server)
+        .await
+        .delete_by_filter(filter.into())
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(report.deleted, 2);
+}
+
+#[tokio::test]
+async fn delete_by_filter_refuses_an_empty_filter() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", mockito::Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+    let index = index(&server).await;
+
+    for filter in [serde_json::json!({}), serde_json::Value::Null] {
+        assert!(index.delete_by_filter(filter).await.is_err());
+    }
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn delete_all_sends_an_empty_filter() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock(
+            "POST",
+            "/v1/collections/col/indexes/idx/documents/delete_by_filter",
+        )
+        .match_body(mockito::Matcher::Json(serde_json::json!({ "filter": {} })))
+        .with_body(r#"{"deleted":5}"#)
+        .create_async()
+        .await;
+
+    let report = index(&server).await.delete_all().await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(report.deleted, 5);
+}
+
+#[tokio::test]
+async fn stream_inserts_are_flushed_in_batches() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/insert")
+        .with_body("{}")
+        .expect(3)
+        .create_async()
+        .await;
+
+    let documents =
+        futures::stream::iter(0..1_000).map(|i| serde_json::json!({ "id": i.to_string() }));
+    let report = index(&server)
+        .await
+        .insert_documents_stream_with_batch_size(documents, 400)
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(
+        report,
+        BatchInsertReport {
+            batches: 3,
+            documents: 1_000,
+        }
+    );
+}
+
+#[tokio::test]
+async fn patches_send_only_the_given_fields() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/patch")
+        .match_header("authorization", "Bearer key")
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "documents": [
+                { "id": "1", "views": 10 },
+                { "id": "2", "version": 4, "title": "New" }
+            ]
+        })))
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let patches = vec![
+        DocumentPatch::new("1").with_field("views", serde_json::json!(10)),
+        DocumentPatch::new("2")
+            .with_version(4)
+            .with_field("title", serde_json::json!("New")),
+    ];
+    let report = index(&server).await.patch_documents(patches).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(report.documents, 2);
+}
+
+#[tokio::test]
+async fn patches_without_an_id_are_rejected() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", mockito::Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+
+    let patches = vec![
+        DocumentPatch::new("1").with_field("views", serde_json::json!(1)),
+        DocumentPatch::new("").with_field("views", serde_json::json!(2)),
+    ];
+    let result = index(&server).await.patch_documents(patches).await;
+
+    assert!(result.is_err());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn dry_run_sends_no_write_requests() {
+    let mut server = mockito::Server::new_async().await;
+    let writes = server
+        .mock(
+            "POST",
+            mockito::Matcher::Regex("/indexes/idx/documents/".to_string()),
+        )
+        .expect(0)
+        .create_async()
+        .await;
+    let index = index(&server).await.with_dry_run(true);
+
+    let inserted = index
+        .insert_documents(vec![
+            serde_json::json!({ "id": "1" }),
+            serde_json::json!({ "id": "2" }),
+            serde_json::json!({ "id": "3" }),
+        ])
+        .await
+        .unwrap();
+    let upserted = index
+        .upsert_documents(vec![serde_json::json!({ "id": "1" })])
+        .await
+        .unwrap();
+    let report = index
+        .upsert_documents_if_version(
+            vec![
+                serde_json::json!({ "id": "1" }),
+                serde_json::json!({ "id": "2" }),
+            ],
+            3,
+        )
+        .await
+        .unwrap();
+
+    writes.assert_async().await;
+    assert!(inserted.dry_run);
+    assert_eq!(inserted.documents, 3);
+    assert!(upserted.dry_run);
+    assert_eq!(upserted.documents, 1);
+    assert!(report.dry_run);
+    assert_eq!(report.documents, 2);
+}
+
+#[tokio::test]
+async fn manager_dry_run_applies_to_its_indexes() {
+    let mut server = mockito::Server::new_async().await;
+    let writes = server
+        .mock(
+            "POST",
+            mockito::Matcher::Regex("/indexes/idx/documents/".to_string()),
+        )
+        .expect(0)
+        .create_async()
+        .await;
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()))
+        .with_dry_run(true);
+    let manager = CollectionManager::new(config).await.unwrap();
+
+    let report = manager
+        .index
+        .set("idx".to_string())
+        .insert_documents(vec![serde_json::json!({ "id": "1" })])
+        .await
+        .unwrap();
+
+    writes.assert_async().await;
+    assert!(report.dry_run);
+    assert_eq!(report.documents, 1);
+}
+
+#[tokio::test]
+async fn writes_accept_no_content_responses() {
+    let mut server = mockito::Server::new_async().await;
+    let insert = server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/insert")
+        .with_status(204)
+        .create_async()
+        .await;
+    let patch = server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/patch")
+        .with_status(204)
+        .create_async()
+        .await;
+    let index = index(&server).await;
+
+    index
+        .insert_documents(vec![serde_json::json!({ "id": "1" })])
+        .await
+        .unwrap();
+    let report = index
+        .patch_documents(vec![DocumentPatch::new("1").with_field("views", 1.into())])
+        .await
+        .unwrap();
+
+    insert.assert_async().await;
+    patch.assert_async().await;
+    assert_eq!(report.documents, 1);
+}
+
+#[tokio::test]
+async fn deletes_accept_no_content_responses() {
+    let mut server = mockito::Server::new_async().await;
+    let delete = server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/delete")
+        .with_status(204)
+        .create_async()
+        .await;
+    let delete_by_filter = server
+        .mock(
+            "POST",
+            "/v1/collections/col/indexes/idx/documents/delete_by_filter",
+        )
+        .with_status(204)
+        .create_async()
+        .await;
+    let index = index(&server).await;
+
+    let report = index
+        .delete_documents_with_report(vec!["1".to_string()])
+        .await
+        .unwrap();
+    let by_filter = index
+        .delete_by_filter(Filter::new().eq("status", "archived").into())
+        .await
+        .unwrap();
+
+    delete.assert_async().await;
+    delete_by_filter.assert_async().await;
+    assert_eq!(report.deleted, 0);
+    assert_eq!(by_filter.deleted, 0);
+}
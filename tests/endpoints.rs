@@ -0,0 +1,114 @@
+use oramacore_client::collection::{CollectionManagerConfig, Endpoints};
+use oramacore_client::{CollectionManager, OramaError, SearchParams};
+
+#[tokio::test]
+async fn endpoint_overrides_reach_auth_and_client() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let jwt = server
+        .mock("POST", "/auth/jwt")
+        .with_body(
+            serde_json::json!({
+                "jwt": "jwt",
+                "writerURL": "http://writer.invalid",
+                "readerApiKey": "reader",
+                "readerURL": "http://reader.invalid",
+                "expiresIn": 3600,
+            })
+            .to_string(),
+        )
+        .expect(2)
+        .create_async()
+        .await;
+    let search = server
+        .mock("POST", "/reader/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .with_body(r#"{"count":0,"hits":[]}"#)
+        .create_async()
+        .await;
+    let insert = server
+        .mock(
+            "POST",
+            "/writer/v1/collections/col/indexes/idx/documents/insert",
+        )
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let endpoints = Endpoints::new()
+        .with_reader_url(format!("{url}/reader"))
+        .with_writer_url(format!("{url}/writer"))
+        .with_auth_jwt_url(format!("{url}/auth/jwt"));
+    let config = CollectionManagerConfig::new("col", "p_key").with_endpoints(endpoints);
+    let manager = CollectionManager::new(config).await.unwrap();
+
+    manager
+        .search::<serde_json::Value>(&SearchParams::new("q"))
+        .await
+        .unwrap();
+    manager
+        .index
+        .set("idx".to_string())
+        .insert_documents(vec![serde_json::json!({ "id": "1" })])
+        .await
+        .unwrap();
+
+    // The configured URLs win over the ones returned with the token
+    jwt.assert_async().await;
+    search.assert_async().await;
+    insert.assert_async().await;
+}
+
+#[tokio::test]
+async fn invalid_endpoints_are_rejected_at_construction() {
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::new().with_reader_url("not a url"));
+
+    let error = CollectionManager::new(config).await.unwrap_err();
+
+    assert!(matches!(error, OramaError::Config { .. }));
+    assert!(error.to_string().contains("reader_url"));
+    assert!(error.to_string().contains("not a url"));
+}
+
+#[tokio::test]
+async fn writes_without_a_writer_url_name_the_setting() {
+    for endpoints in [
+        Endpoints::new(),
+        Endpoints::from_base_url("http://localhost").with_writer_url(""),
+    ] {
+        let config = CollectionManagerConfig::new("col", "key").with_endpoints(endpoints);
+        let manager = CollectionManager::new(config).await.unwrap();
+
+        let error = manager
+            .index
+            .set("idx".to_string())
+            .insert_documents(vec![serde_json::json!({ "id": "1" })])
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, OramaError::Config { .. }));
+        assert!(error.to_string().contains("ClusterConfig::with_writer_url"));
+    }
+}
+
+#[tokio::test]
+async fn invalid_endpoint_errors_name_the_field() {
+    for (field, endpoints) in [
+        (
+            "writer_url",
+            Endpoints::from_base_url("http://localhost").with_writer_url("writer"),
+        ),
+        (
+            "auth_jwt_url",
+            Endpoints::from_base_url("http://localhost").with_auth_jwt_url("jwt"),
+        ),
+    ] {
+        let config = CollectionManagerConfig::new("col", "key").with_endpoints(endpoints);
+
+        let error = CollectionManager::new(config).await.unwrap_err();
+
+        assert!(matches!(error, OramaError::Config { .. }));
+        assert!(error.to_string().contains(field), "{error}");
+    }
+}
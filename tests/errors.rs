@@ -0,0 +1,85 @@
+use oramacore_client::{OramaError, SearchParams};
+
+mod common;
+
+use common::manager;
+
+#[tokio::test]
+async fn api_errors_name_the_failed_request() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .with_status(404)
+        .with_body("collection not found")
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/insert")
+        .with_status(500)
+        .with_body("boom")
+        .create_async()
+        .await;
+    let manager = manager(&server).await;
+
+    let search_error = manager
+        .search::<serde_json::Value>(&SearchParams::new("q"))
+        .await
+        .unwrap_err();
+    let insert_error = manager
+        .index
+        .set("idx".to_string())
+        .insert_documents(vec![serde_json::json!({ "id": "1" })])
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        search_error.to_string(),
+        "API error (status 404) on POST /v1/collections/col/search: collection not found"
+    );
+    assert_eq!(
+        insert_error.to_string(),
+        "API error (status 500) on POST /v1/collections/col/indexes/idx/documents/insert: boom"
+    );
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Product {
+    #[allow(dead_code)]
+    price: f64,
+}
+
+#[tokio::test]
+async fn deserialize_errors_carry_the_type_and_a_body_snippet() {
+    let mut server = mockito::Server::new_async().await;
+    let body = format!(
+        r#"{{"count":1,"hits":[{{"id":"1","score":1.0,"document":{{"price":"{}"}}}}]}}"#,
+        "9".repeat(1000)
+    );
+    server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .with_body(&body)
+        .create_async()
+        .await;
+
+    let error = manager(&server)
+        .await
+        .search::<Product>(&SearchParams::new("shoes"))
+        .await
+        .unwrap_err();
+
+    let OramaError::Deserialize {
+        expected_type,
+        raw_snippet,
+        ..
+    } = &error
+    else {
+        panic!("expected a deserialize error, got {error:?}");
+    };
+    assert!(expected_type.contains("SearchResult"), "{expected_type}");
+    assert!(expected_type.contains("Product"), "{expected_type}");
+    assert!(body.starts_with(raw_snippet.as_str()));
+    assert!(raw_snippet.len() < body.len());
+    assert!(error.to_string().contains(r#"{"count":1"#));
+}
@@ -0,0 +1,100 @@
+use oramacore_client::Hook;
+
+mod common;
+
+use common::manager;
+
+#[tokio::test]
+async fn passing_hook_test_returns_output_and_logs() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/hooks/test")
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "name": "BeforeRetrieval",
+            "code": "return input",
+            "input": { "term": "shoes" }
+        })))
+        .with_body(r#"{"output":{"term":"shoes"},"logs":["called"]}"#)
+        .create_async()
+        .await;
+
+    let result = manager(&server)
+        .await
+        .hooks
+        .test_code(
+            Hook::BeforeRetrieval,
+            "return input",
+            serde_json::json!({ "term": "shoes" }),
+        )
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert!(result.is_success());
+    assert_eq!(result.output, Some(serde_json::json!({ "term": "shoes" })));
+    assert_eq!(result.logs, ["called"]);
+}
+
+#[tokio::test]
+async fn failing_hook_test_reports_the_error() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/v1/collections/col/hooks/list")
+        .with_body(r#"{"hooks":{"BeforeAnswer":"throw new Error('nope')"}}"#)
+        .create_async()
+        .await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/hooks/test")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "name": "BeforeAnswer",
+            "code": "throw new Error('nope')"
+        })))
+        .with_body(r#"{"error":"Error: nope"}"#)
+        .create_async()
+        .await;
+
+    let result = manager(&server)
+        .await
+        .hooks
+        .test(Hook::BeforeAnswer, serde_json::json!({}))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert!(!result.is_success());
+    assert_eq!(result.error.as_deref(), Some("Error: nope"));
+    assert_eq!(result.output, None);
+}
+
+#[tokio::test]
+async fn testing_an_unset_hook_fails() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/v1/collections/col/hooks/list")
+        .with_body(r#"{"hooks":{"BeforeAnswer":null}}"#)
+        .create_async()
+        .await;
+
+    let result = manager(&server)
+        .await
+        .hooks
+        .test(Hook::BeforeAnswer, serde_json::json!({}))
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn list_keeps_unknown_hooks() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/v1/collections/col/hooks/list")
+        .with_body(r#"{"hooks":{"BeforeInsert":"code","AfterAnswer":null}}"#)
+        .create_async()
+        .await;
+
+    let hooks = manager(&server).await.hooks.list().await.unwrap();
+
+    assert_eq!(hooks[&Hook::BeforeInsert].as_deref(), Some("code"));
+    assert_eq!(hooks[&Hook::Other("AfterAnswer".to_string())], None);
+}
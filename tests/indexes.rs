@@ -0,0 +1,166 @@
+use oramacore_client::collection::CreateIndexParams;
+use oramacore_client::OramaError;
+
+mod common;
+
+use common::manager;
+
+async fn mock_collection(server: &mut mockito::Server, index_ids: &[&str]) -> mockito::Mock {
+    let indexes: Vec<_> = index_ids
+        .iter()
+        .map(|id| {
+            serde_json::json!({
+                "id": id,
+                "document_count": 0,
+                "fields": [],
+                "automatically_chosen_properties": {}
+            })
+        })
+        .collect();
+    server
+        .mock("GET", "/v1/collections/col")
+        .with_body(
+            serde_json::json!({
+                "id": "col",
+                "description": null,
+                "document_count": 0,
+                "indexes": indexes
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn default_index_selects_the_only_index() {
+    let mut server = mockito::Server::new_async().await;
+    mock_collection(&mut server, &["only"]).await;
+    let insert = server
+        .mock("POST", "/v1/collections/col/indexes/only/documents/insert")
+        .with_body("{}")
+        .create_async()
+        .await;
+    let manager = manager(&server).await;
+
+    assert_eq!(manager.indexes().await.unwrap(), ["only"]);
+    manager
+        .default_index()
+        .await
+        .unwrap()
+        .insert_documents(vec![serde_json::json!({ "id": "1" })])
+        .await
+        .unwrap();
+
+    insert.assert_async().await;
+}
+
+#[tokio::test]
+async fn default_index_lists_the_candidates_when_ambiguous() {
+    let mut server = mockito::Server::new_async().await;
+    mock_collection(&mut server, &["products", "reviews"]).await;
+
+    let error = manager(&server).await.default_index().await.unwrap_err();
+
+    let message = error.to_string();
+    assert!(message.contains("products, reviews"), "{message}");
+}
+
+fn index_params() -> CreateIndexParams {
+    CreateIndexParams {
+        id: Some("idx".to_string()),
+        embeddings: None,
+    }
+}
+
+/// Mock index creation answering with `status` and `body`, then a document
+/// insert into the created index
+async fn mock_ensure(
+    server: &mut mockito::Server,
+    status: usize,
+    body: &str,
+) -> (mockito::Mock, mockito::Mock) {
+    let create = server
+        .mock("POST", "/v1/collections/col/indexes/create")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "id": "idx" }),
+        ))
+        .with_status(status)
+        .with_body(body)
+        .create_async()
+        .await;
+    let insert = server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/insert")
+        .with_body("{}")
+        .create_async()
+        .await;
+    (create, insert)
+}
+
+async fn ensure_and_insert(server: &mockito::Server) -> oramacore_client::Result<()> {
+    manager(server)
+        .await
+        .ensure_index(index_params())
+        .await?
+        .insert_documents(vec![serde_json::json!({ "id": "1" })])
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn ensure_index_creates_a_missing_index() {
+    let mut server = mockito::Server::new_async().await;
+    let (create, insert) = mock_ensure(&mut server, 200, "{}").await;
+
+    ensure_and_insert(&server).await.unwrap();
+
+    create.assert_async().await;
+    insert.assert_async().await;
+}
+
+#[tokio::test]
+async fn ensure_index_accepts_an_existing_index() {
+    for (status, body) in [
+        (409, r#"{"message":"conflict"}"#),
+        (400, r#"{"message":"Index idx already exists"}"#),
+    ] {
+        let mut server = mockito::Server::new_async().await;
+        let (create, insert) = mock_ensure(&mut server, status, body).await;
+
+        ensure_and_insert(&server).await.unwrap();
+
+        create.assert_async().await;
+        insert.assert_async().await;
+    }
+}
+
+#[tokio::test]
+async fn ensure_index_returns_other_errors() {
+    let mut server = mockito::Server::new_async().await;
+    let (create, _) = mock_ensure(&mut server, 500, r#"{"message":"boom"}"#).await;
+
+    let error = ensure_and_insert(&server).await.unwrap_err();
+
+    create.assert_async().await;
+    assert!(
+        matches!(error, OramaError::Api { status: 500, .. }),
+        "{error}"
+    );
+}
+
+#[tokio::test]
+async fn ensure_index_requires_an_id() {
+    let server = mockito::Server::new_async().await;
+    let params = CreateIndexParams {
+        id: None,
+        embeddings: None,
+    };
+
+    let error = manager(&server)
+        .await
+        .ensure_index(params)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, OramaError::Config { .. }));
+}
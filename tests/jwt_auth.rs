@@ -0,0 +1,163 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use oramacore_client::collection::{ClusterConfig, CollectionManagerConfig};
+use oramacore_client::{CollectionManager, OramaError, SearchParams};
+
+async fn manager(server: &mockito::Server) -> CollectionManager {
+    let config = CollectionManagerConfig::new("col", "p_key")
+        .with_cluster(
+            ClusterConfig::new()
+                .with_read_url(server.url())
+                .with_writer_url(server.url()),
+        )
+        .with_auth_jwt_url(format!("{}/api/user/jwt", server.url()));
+    CollectionManager::new(config).await.unwrap()
+}
+
+/// Mock the JWT endpoint, handing out `reader-<n>` keys on the n-th fetch
+async fn mock_jwt(server: &mut mockito::Server, fetches: usize) -> mockito::Mock {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let url = server.url();
+    server
+        .mock("POST", "/api/user/jwt")
+        .with_body_from_request(move |_| {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            serde_json::json!({
+                "jwt": format!("jwt-{n}"),
+                "writerURL": url,
+                "readerApiKey": format!("reader-{n}"),
+                "readerURL": url,
+                "expiresIn": 3600,
+            })
+            .to_string()
+            .into()
+        })
+        .expect(fetches)
+        .create_async()
+        .await
+}
+
+fn api_key(key: &str) -> mockito::Matcher {
+    mockito::Matcher::UrlEncoded("api-key".into(), key.into())
+}
+
+#[tokio::test]
+async fn request_is_retried_once_with_a_fresh_token_after_a_401() {
+    let mut server = mockito::Server::new_async().await;
+    let jwt = mock_jwt(&mut server, 2).await;
+    let rejected = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(api_key("reader-1"))
+        .with_status(401)
+        .expect(1)
+        .create_async()
+        .await;
+    let accepted = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(api_key("reader-2"))
+        .with_body(r#"{"count":0,"hits":[]}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let result = manager(&server)
+        .await
+        .search::<serde_json::Value>(&SearchParams::new("q"))
+        .await;
+
+    assert!(result.is_ok());
+    jwt.assert_async().await;
+    rejected.assert_async().await;
+    accepted.assert_async().await;
+}
+
+#[tokio::test]
+async fn a_second_401_is_returned_without_another_refresh() {
+    let mut server = mockito::Server::new_async().await;
+    let jwt = mock_jwt(&mut server, 2).await;
+    let search = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .with_status(401)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let error = manager(&server)
+        .await
+        .search::<serde_json::Value>(&SearchParams::new("q"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, OramaError::Auth { .. }));
+    jwt.assert_async().await;
+    search.assert_async().await;
+}
+
+#[tokio::test]
+async fn reader_and_writer_tokens_are_requested_with_their_own_scope() {
+    let mut server = mockito::Server::new_async().await;
+    let url = server.url();
+    let token = |scope: &str| {
+        serde_json::json!({
+            "jwt": format!("jwt-{scope}"),
+            "writerURL": url,
+            "readerApiKey": format!("reader-{scope}"),
+            "readerURL": url,
+            "expiresIn": 3600,
+        })
+        .to_string()
+    };
+    let read_scope = server
+        .mock("POST", "/api/user/jwt")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "collectionId": "col", "scope": "read" }),
+        ))
+        .with_body(token("read"))
+        .expect(1)
+        .create_async()
+        .await;
+    let write_scope = server
+        .mock("POST", "/api/user/jwt")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "collectionId": "col", "scope": "write" }),
+        ))
+        .with_body(token("write"))
+        .expect(1)
+        .create_async()
+        .await;
+    let search = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(api_key("reader-read"))
+        .with_body(r#"{"count":0,"hits":[]}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let insert = server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/insert")
+        .match_header("authorization", "Bearer jwt-write")
+        .with_body("{}")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let manager = manager(&server).await;
+    let index = manager.index.set("idx".to_string());
+    for _ in 0..2 {
+        manager
+            .search::<serde_json::Value>(&SearchParams::new("q"))
+            .await
+            .unwrap();
+        index
+            .insert_documents(vec![serde_json::json!({ "id": "1" })])
+            .await
+            .unwrap();
+    }
+
+    // One fetch per scope: each token is cached separately
+    read_scope.assert_async().await;
+    write_scope.assert_async().await;
+    search.assert_async().await;
+    insert.assert_async().await;
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use oramacore_client::auth::{ApiKeyAuth, Auth, AuthConfig};
+use oramacore_client::client::OramaClient;
+use oramacore_client::stream_manager::{AnswerConfig, CreateAiSessionConfig};
+use oramacore_client::{LlmConfig, LlmProvider, OramaCoreStream};
+
+fn llm(model: &str) -> LlmConfig {
+    LlmConfig {
+        provider: LlmProvider::OpenAI,
+        model: model.to_string(),
+    }
+}
+
+async fn mock_answer(server: &mut mockito::Server, model: &str) -> mockito::Mock {
+    server
+        .mock("POST", "/v1/collections/col/ai/answer")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "LLMConfig": { "provider": "openai", "model": model }
+        })))
+        .with_body(r#"{"answer":"hi"}"#)
+        .expect(1)
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn changed_llm_config_applies_to_the_next_answer() {
+    let mut server = mockito::Server::new_async().await;
+    let small = mock_answer(&mut server, "gpt-4o-mini").await;
+    let large = mock_answer(&mut server, "gpt-4o").await;
+
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url(server.url())),
+        Arc::new(reqwest::Client::new()),
+    );
+    let session = Arc::new(
+        OramaCoreStream::with_config(
+            "col".to_string(),
+            OramaClient::new(auth).unwrap(),
+            CreateAiSessionConfig::new().with_llm_config(llm("gpt-4o-mini")),
+        )
+        .await
+        .unwrap(),
+    );
+
+    session.answer(AnswerConfig::new("first")).await.unwrap();
+    session.set_llm_config(llm("gpt-4o")).await;
+    session.answer(AnswerConfig::new("second")).await.unwrap();
+
+    small.assert_async().await;
+    large.assert_async().await;
+}
@@ -0,0 +1,258 @@
+use oramacore_client::collection::CreateIndexParams;
+use oramacore_client::manager::{
+    CollectionCreation, CreateCollectionParams, OramaCoreManager, OramaCoreManagerConfig,
+};
+use oramacore_client::{EmbeddingsConfig, EmbeddingsModel};
+
+async fn manager(server: &mockito::Server) -> OramaCoreManager {
+    OramaCoreManager::new(OramaCoreManagerConfig {
+        url: server.url(),
+        master_api_key: "master".to_string(),
+    })
+    .await
+    .unwrap()
+}
+
+fn collection_body(id: &str) -> String {
+    serde_json::json!({
+        "id": id,
+        "description": null,
+        "document_count": 0,
+        "indexes": []
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn exists_distinguishes_present_absent_and_failing_collections() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/v1/collections/present")
+        .with_body(collection_body("present"))
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/v1/collections/absent")
+        .with_status(404)
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/v1/collections/broken")
+        .with_status(500)
+        .create_async()
+        .await;
+    let manager = manager(&server).await;
+
+    assert!(manager.collection.exists("present").await.unwrap());
+    assert!(!manager.collection.exists("absent").await.unwrap());
+    assert!(manager.collection.exists("broken").await.is_err());
+}
+
+#[tokio::test]
+async fn create_if_absent_only_creates_missing_collections() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/v1/collections/present")
+        .with_body(collection_body("present"))
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/v1/collections/absent")
+        .with_status(404)
+        .create_async()
+        .await;
+    let create = server
+        .mock("POST", "/v1/collections/create")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "id": "absent" }),
+        ))
+        .with_body(
+            serde_json::json!({
+                "id": "absent",
+                "writeAPIKey": "w",
+                "readonlyAPIKey": "r"
+            })
+            .to_string(),
+        )
+        .expect(1)
+        .create_async()
+        .await;
+    let manager = manager(&server).await;
+
+    let existing = manager
+        .collection
+        .create_if_absent(CreateCollectionParams::new("present"))
+        .await
+        .unwrap();
+    let created = manager
+        .collection
+        .create_if_absent(CreateCollectionParams::new("absent"))
+        .await
+        .unwrap();
+
+    assert!(matches!(existing, CollectionCreation::Existing(c) if c.id == "present"));
+    assert!(matches!(created, CollectionCreation::Created(c) if c.id == "absent"));
+    create.assert_async().await;
+}
+
+async fn mock_create(server: &mut mockito::Server) -> mockito::Mock {
+    server
+        .mock("POST", "/v1/collections/create")
+        .match_header("authorization", "Bearer master")
+        .with_body(
+            serde_json::json!({
+                "id": "col",
+                "writeAPIKey": "write",
+                "readonlyAPIKey": "read"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await
+}
+
+fn index_params() -> CreateIndexParams {
+    CreateIndexParams {
+        id: Some("idx".to_string()),
+        embeddings: None,
+    }
+}
+
+#[tokio::test]
+async fn create_collection_with_index_returns_a_ready_index() {
+    let mut server = mockito::Server::new_async().await;
+    let create = mock_create(&mut server).await;
+    let create_index = server
+        .mock("POST", "/v1/collections/col/indexes/create")
+        .match_header("authorization", "Bearer write")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "id": "idx" }),
+        ))
+        .with_body("{}")
+        .create_async()
+        .await;
+    let insert = server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/insert")
+        .match_header("authorization", "Bearer write")
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let (collection, index) = manager(&server)
+        .await
+        .create_collection_with_index(CreateCollectionParams::new("col"), index_params())
+        .await
+        .unwrap();
+    index
+        .insert_documents(vec![serde_json::json!({ "id": "1" })])
+        .await
+        .unwrap();
+
+    assert_eq!(collection.write_api_key, "write");
+    create.assert_async().await;
+    create_index.assert_async().await;
+    insert.assert_async().await;
+}
+
+#[tokio::test]
+async fn create_collection_with_index_deletes_the_collection_on_failure() {
+    let mut server = mockito::Server::new_async().await;
+    mock_create(&mut server).await;
+    server
+        .mock("POST", "/v1/collections/col/indexes/create")
+        .with_status(500)
+        .with_body("boom")
+        .create_async()
+        .await;
+    let cleanup = server
+        .mock("POST", "/v1/collections/delete")
+        .match_body(mockito::Matcher::Json(
+            serde_json::json!({ "collection_id_to_delete": "col" }),
+        ))
+        .with_body("{}")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let result = manager(&server)
+        .await
+        .create_collection_with_index(CreateCollectionParams::new("col"), index_params())
+        .await;
+
+    assert!(result.is_err());
+    cleanup.assert_async().await;
+}
+
+fn mock_create_with_embeddings(
+    server: &mut mockito::Server,
+    embeddings: serde_json::Value,
+) -> mockito::Mock {
+    server
+        .mock("POST", "/v1/collections/create")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "id": "col", "embeddings": embeddings }),
+        ))
+        .match_request(|request| {
+            let body: serde_json::Value = serde_json::from_slice(request.body().unwrap()).unwrap();
+            body.get("embeddings_model").is_none()
+        })
+        .with_body(
+            serde_json::json!({
+                "id": "col",
+                "writeAPIKey": "write",
+                "readonlyAPIKey": "read"
+            })
+            .to_string(),
+        )
+        .expect(1)
+}
+
+#[tokio::test]
+async fn create_sends_the_full_embeddings_config_with_the_model_once() {
+    let mut server = mockito::Server::new_async().await;
+    let create = mock_create_with_embeddings(
+        &mut server,
+        serde_json::json!({ "model": "BGESmall", "document_fields": ["title", "body"] }),
+    )
+    .create_async()
+    .await;
+
+    let params = CreateCollectionParams::new("col").with_embeddings_config(
+        EmbeddingsConfig::new()
+            .with_model(EmbeddingsModel::BgeSmall)
+            .with_document_fields(vec!["title".to_string(), "body".to_string()]),
+    );
+    manager(&server)
+        .await
+        .collection
+        .create(params)
+        .await
+        .unwrap();
+
+    create.assert_async().await;
+}
+
+#[tokio::test]
+async fn create_moves_the_model_shortcut_into_the_embeddings_config() {
+    let mut server = mockito::Server::new_async().await;
+    let create = mock_create_with_embeddings(
+        &mut server,
+        serde_json::json!({ "model": "BGESmall", "document_fields": ["title"] }),
+    )
+    .create_async()
+    .await;
+
+    let params = CreateCollectionParams::new("col")
+        .with_embeddings_model(EmbeddingsModel::BgeSmall)
+        .with_embeddings_config(
+            EmbeddingsConfig::new().with_document_fields(vec!["title".to_string()]),
+        );
+    manager(&server)
+        .await
+        .collection
+        .create(params)
+        .await
+        .unwrap();
+
+    create.assert_async().await;
+}
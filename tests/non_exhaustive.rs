@@ -0,0 +1,41 @@
+//! Downstream matches on the crate's public enums need a wildcard arm; see the
+//! `compile_fail` example on `SearchMode` for the exhaustive case.
+
+use oramacore_client::stream_manager::StreamChunk;
+use oramacore_client::{LlmProvider, SearchMode};
+
+fn describe_mode(mode: &SearchMode) -> &'static str {
+    match mode {
+        SearchMode::Fulltext => "fulltext",
+        SearchMode::Vector => "vector",
+        SearchMode::Hybrid => "hybrid",
+        SearchMode::Auto => "auto",
+        _ => "unknown",
+    }
+}
+
+fn describe_provider(provider: &LlmProvider) -> &'static str {
+    match provider {
+        LlmProvider::OpenAI => "openai",
+        _ => "other",
+    }
+}
+
+fn describe_chunk(chunk: &StreamChunk) -> &'static str {
+    match chunk {
+        StreamChunk::Content(_) => "content",
+        StreamChunk::Done => "done",
+        _ => "other",
+    }
+}
+
+#[test]
+fn matches_with_a_wildcard_arm_compile() {
+    assert_eq!(describe_mode(&SearchMode::Hybrid), "hybrid");
+    assert_eq!(describe_provider(&LlmProvider::Claude), "other");
+    assert_eq!(
+        describe_chunk(&StreamChunk::Content("hi".to_string())),
+        "content"
+    );
+    assert_eq!(describe_chunk(&StreamChunk::Heartbeat), "other");
+}
@@ -0,0 +1,62 @@
+use oramacore_client::stream_manager::AnswerConfig;
+use oramacore_client::Role;
+
+mod common;
+
+use common::session;
+
+async fn mock_answer(server: &mut mockito::Server, query: &str, answer: &str) -> mockito::Mock {
+    server
+        .mock("POST", "/v1/collections/col/ai/answer")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "query": query,
+            "datasourceIDs": ["docs"]
+        })))
+        .with_body(serde_json::json!({ "answer": answer }).to_string())
+        .expect(1)
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn edit_last_and_regenerate_sends_the_new_query() {
+    let mut server = mockito::Server::new_async().await;
+    let original = mock_answer(&mut server, "red shoes", "Red ones").await;
+    let edited = mock_answer(&mut server, "blue shoes", "Blue ones").await;
+    let session = session(&server).await;
+
+    session
+        .answer(AnswerConfig::new("red shoes").with_datasource_ids(vec!["docs".to_string()]))
+        .await
+        .unwrap();
+    let answer = session
+        .edit_last_and_regenerate("blue shoes".to_string(), false)
+        .await
+        .unwrap();
+
+    original.assert_async().await;
+    edited.assert_async().await;
+    assert_eq!(answer, "Blue ones");
+    let messages = session.get_messages().await;
+    assert_eq!(messages.len(), 2);
+    assert!(matches!(messages[0].role, Role::User));
+    assert_eq!(messages[0].content, "blue shoes");
+    assert_eq!(messages[1].content, "Blue ones");
+    assert_eq!(session.interaction_count().await, 1);
+    assert_eq!(
+        session.last_interaction().await.unwrap().query,
+        "blue shoes"
+    );
+}
+
+#[tokio::test]
+async fn edit_last_and_regenerate_needs_a_previous_answer() {
+    let server = mockito::Server::new_async().await;
+    let session = session(&server).await;
+
+    assert!(session
+        .edit_last_and_regenerate("blue shoes".to_string(), false)
+        .await
+        .is_err());
+}
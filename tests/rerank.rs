@@ -0,0 +1,53 @@
+mod common;
+
+use common::manager;
+
+#[tokio::test]
+async fn rerank_returns_the_original_documents_by_score() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/rerank")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "query": "shoes",
+            "documents": ["boots", "sneakers", "hats"],
+            "top_n": 2
+        })))
+        .with_body(
+            r#"{"results":[{"index":0,"score":0.4},{"index":1,"score":0.9},{"index":2,"score":0.1}]}"#,
+        )
+        .create_async()
+        .await;
+
+    let ranked = manager(&server)
+        .await
+        .ai
+        .rerank("shoes", vec!["boots", "sneakers", "hats"], Some(2))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(ranked.len(), 2);
+    assert_eq!((ranked[0].index, ranked[0].document), (1, "sneakers"));
+    assert_eq!((ranked[1].index, ranked[1].document), (0, "boots"));
+    assert_eq!(ranked[0].score, 0.9);
+}
+
+#[tokio::test]
+async fn rerank_rejects_unknown_document_indexes() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/v1/collections/col/rerank")
+        .match_query(mockito::Matcher::Any)
+        .with_body(r#"{"results":[{"index":3,"score":0.4}]}"#)
+        .create_async()
+        .await;
+
+    let result = manager(&server)
+        .await
+        .ai
+        .rerank("shoes", vec!["boots"], None)
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,154 @@
+use futures::StreamExt;
+use oramacore_client::{SearchMode, SearchParams};
+
+mod common;
+
+use common::manager;
+
+#[tokio::test]
+async fn count_returns_the_count_without_hits() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "term": "shoes",
+            "mode": "fulltext",
+            "limit": 0,
+            "where": { "in_stock": true }
+        })))
+        .with_body(r#"{"count":42,"hits":[]}"#)
+        .create_async()
+        .await;
+
+    let query = SearchParams::new("shoes")
+        .with_mode(SearchMode::Fulltext)
+        .with_limit(10)
+        .with_where(serde_json::json!({ "in_stock": true }));
+    let count = manager(&server).await.count(&query).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(count, 42);
+}
+
+#[tokio::test]
+async fn search_pages_follows_the_cursor() {
+    let mut server = mockito::Server::new_async().await;
+    let query = SearchParams::new("shoes");
+    let first = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::Json(
+            serde_json::to_value(&query).unwrap(),
+        ))
+        .with_body(
+            r#"{"count":2,"hits":[{"id":"1","score":1.0,"document":{}}],"next_cursor":"page-2"}"#,
+        )
+        .create_async()
+        .await;
+    let second = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "term": "shoes", "cursor": "page-2" }),
+        ))
+        .with_body(r#"{"count":2,"hits":[{"id":"2","score":0.5,"document":{}}]}"#)
+        .create_async()
+        .await;
+
+    let manager = manager(&server).await;
+    let pages: Vec<_> = manager
+        .search_pages::<serde_json::Value>(&query)
+        .collect()
+        .await;
+
+    first.assert_async().await;
+    second.assert_async().await;
+    let ids: Vec<String> = pages
+        .into_iter()
+        .flat_map(|page| page.unwrap().hits)
+        .map(|hit| hit.id)
+        .collect();
+    assert_eq!(ids, ["1", "2"]);
+}
+
+#[tokio::test]
+async fn search_measures_elapsed_time_on_the_monotonic_clock() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .with_body(r#"{"count":0,"hits":[],"elapsed":{"raw":99999999,"formatted":"bogus"}}"#)
+        .create_async()
+        .await;
+
+    let result = manager(&server)
+        .await
+        .search::<serde_json::Value>(&SearchParams::new("shoes"))
+        .await
+        .unwrap();
+
+    let elapsed = result.elapsed.unwrap();
+    assert!(elapsed.raw < 10_000);
+    assert_eq!(
+        elapsed.formatted,
+        oramacore_client::utils::format_duration(elapsed.raw)
+    );
+}
+
+#[tokio::test]
+async fn search_pages_stops_on_a_repeated_cursor() {
+    let mut server = mockito::Server::new_async().await;
+    let query = SearchParams::new("shoes");
+    let first = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::Json(
+            serde_json::to_value(&query).unwrap(),
+        ))
+        .with_body(r#"{"count":2,"hits":[],"next_cursor":"page-2"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let repeated = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "cursor": "page-2" }),
+        ))
+        .with_body(r#"{"count":2,"hits":[],"next_cursor":"page-2"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let manager = manager(&server).await;
+    let pages: Vec<_> = manager
+        .search_pages::<serde_json::Value>(&query)
+        .collect()
+        .await;
+
+    first.assert_async().await;
+    repeated.assert_async().await;
+    assert_eq!(pages.len(), 2);
+}
+
+#[tokio::test]
+async fn search_pages_stops_on_an_empty_cursor() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .with_body(r#"{"count":1,"hits":[],"next_cursor":""}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let manager = manager(&server).await;
+    let pages: Vec<_> = manager
+        .search_pages::<serde_json::Value>(&SearchParams::new("shoes"))
+        .collect()
+        .await;
+
+    mock.assert_async().await;
+    assert_eq!(pages.len(), 1);
+}
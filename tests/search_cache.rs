@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use oramacore_client::cache::SearchCacheConfig;
+use oramacore_client::collection::{CollectionManagerConfig, Endpoints};
+use oramacore_client::{CollectionManager, SearchParams};
+
+async fn manager(server: &mockito::Server, ttl: Duration) -> CollectionManager {
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()))
+        .with_search_cache(SearchCacheConfig::new(10, ttl));
+    CollectionManager::new(config).await.unwrap()
+}
+
+async fn mock_search(server: &mut mockito::Server, hits: usize) -> mockito::Mock {
+    server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::Any)
+        .with_body(r#"{"count":1,"hits":[]}"#)
+        .expect(hits)
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn identical_searches_are_served_from_the_cache() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = mock_search(&mut server, 1).await;
+    let manager = manager(&server, Duration::from_secs(60)).await;
+    let query = SearchParams::new("shoes");
+
+    for _ in 0..3 {
+        let result = manager
+            .search_cached::<serde_json::Value>(&query)
+            .await
+            .unwrap();
+        assert_eq!(result.count, 1);
+    }
+
+    mock.assert_async().await;
+    assert_eq!(manager.search_cache().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn expired_results_are_fetched_again() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = mock_search(&mut server, 2).await;
+    let manager = manager(&server, Duration::from_millis(50)).await;
+    let query = SearchParams::new("shoes");
+
+    manager
+        .search_cached::<serde_json::Value>(&query)
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    manager
+        .search_cached::<serde_json::Value>(&query)
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn inserts_invalidate_cached_results() {
+    let mut server = mockito::Server::new_async().await;
+    let search = mock_search(&mut server, 2).await;
+    let insert = server
+        .mock("POST", "/v1/collections/col/indexes/idx/documents/insert")
+        .with_body("{}")
+        .create_async()
+        .await;
+    let manager = manager(&server, Duration::from_secs(60)).await;
+    let query = SearchParams::new("shoes");
+
+    manager
+        .search_cached::<serde_json::Value>(&query)
+        .await
+        .unwrap();
+    manager
+        .index
+        .set("idx".to_string())
+        .insert_documents(vec![serde_json::json!({ "id": "1" })])
+        .await
+        .unwrap();
+    assert!(manager.search_cache().unwrap().is_empty());
+    manager
+        .search_cached::<serde_json::Value>(&query)
+        .await
+        .unwrap();
+
+    insert.assert_async().await;
+    search.assert_async().await;
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use oramacore_client::auth::{ApiKeyAuth, Auth, AuthConfig};
+use oramacore_client::client::OramaClient;
+use oramacore_client::stream_manager::StreamConfig;
+use oramacore_client::{CollectionManager, OramaCloud, OramaCoreManager, OramaCoreStream};
+
+fn assert_send_sync<T: Send + Sync + 'static>() {}
+fn assert_clone<T: Clone>() {}
+
+#[test]
+fn clients_can_be_shared_across_tasks() {
+    assert_send_sync::<CollectionManager>();
+    assert_send_sync::<OramaCloud>();
+    assert_send_sync::<OramaCoreStream>();
+    assert_send_sync::<OramaCoreManager>();
+    assert_clone::<CollectionManager>();
+    assert_clone::<OramaCloud>();
+}
+
+#[tokio::test]
+async fn stream_config_can_be_changed_through_a_shared_session() {
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url("http://localhost")),
+        Arc::new(reqwest::Client::new()),
+    );
+    let session = Arc::new(
+        OramaCoreStream::new("col".to_string(), OramaClient::new(auth).unwrap())
+            .await
+            .unwrap(),
+    );
+
+    let shared = Arc::clone(&session);
+    tokio::spawn(async move {
+        shared.set_stream_config(StreamConfig {
+            max_retries: 7,
+            ..StreamConfig::default()
+        });
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(session.get_stream_config().max_retries, 7);
+}
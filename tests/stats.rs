@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+
+mod common;
+
+use common::manager;
+
+async fn mock_stats(server: &mut mockito::Server, count: u64, hits: usize) -> mockito::Mock {
+    server
+        .mock("GET", "/v1/collections/col/stats")
+        .match_query(mockito::Matcher::Any)
+        .with_body(format!(r#"{{"document_count":{count},"indexes":2}}"#))
+        .expect(hits)
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn watch_stats_yields_every_poll() {
+    let mut server = mockito::Server::new_async().await;
+    let first = mock_stats(&mut server, 1, 1).await;
+    let second = mock_stats(&mut server, 2, 1).await;
+    let manager = manager(&server).await;
+
+    let counts: Vec<u64> = manager
+        .collections
+        .watch_stats(Duration::from_millis(10))
+        .take(2)
+        .map(|stats| stats.unwrap().document_count)
+        .collect()
+        .await;
+
+    first.assert_async().await;
+    second.assert_async().await;
+    assert_eq!(counts, [1, 2]);
+}
+
+#[tokio::test]
+async fn watch_stats_changes_skips_unchanged_snapshots() {
+    let mut server = mockito::Server::new_async().await;
+    let unchanged = mock_stats(&mut server, 1, 3).await;
+    let changed = mock_stats(&mut server, 5, 1).await;
+    let manager = manager(&server).await;
+
+    let snapshots: Vec<_> = manager
+        .collections
+        .watch_stats_changes(Duration::from_millis(10))
+        .take(2)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    unchanged.assert_async().await;
+    changed.assert_async().await;
+    let counts: Vec<u64> = snapshots.iter().map(|s| s.document_count).collect();
+    assert_eq!(counts, [1, 5]);
+    assert_eq!(snapshots[1].fields["indexes"], 2);
+}
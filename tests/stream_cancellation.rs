@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use oramacore_client::stream_manager::{AnswerConfig, StreamChunk};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+mod common;
+
+use common::session_at;
+
+/// Serve an SSE response that emits a content frame every 50ms until the
+/// client disconnects, which is reported on the returned channel
+async fn serve_endless_sse() -> (String, oneshot::Receiver<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (closed_tx, closed_rx) = oneshot::channel();
+
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 8192];
+        let _ = socket.read(&mut buf).await;
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\n")
+            .await
+            .unwrap();
+
+        loop {
+            let frame = b"data: {\"content\":\"x\"}\n\n";
+            if socket.write_all(frame).await.is_err() || socket.flush().await.is_err() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let _ = closed_tx.send(());
+    });
+
+    (url, closed_rx)
+}
+
+#[tokio::test]
+async fn dropping_the_stream_cancels_its_background_task() {
+    let (url, closed) = serve_endless_sse().await;
+    let session = session_at(url).await;
+
+    let mut stream = session.answer_stream(AnswerConfig::new("q")).await.unwrap();
+    while let Some(chunk) = stream.next().await {
+        if let StreamChunk::Content(_) = chunk.unwrap() {
+            break;
+        }
+    }
+    drop(stream);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let response_at_drop = session.last_interaction().await.unwrap().response;
+
+    tokio::time::timeout(Duration::from_secs(5), closed)
+        .await
+        .expect("connection still open after the stream was dropped")
+        .unwrap();
+    assert_eq!(
+        session.last_interaction().await.unwrap().response,
+        response_at_drop
+    );
+}
@@ -0,0 +1,97 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use futures::StreamExt;
+use oramacore_client::auth::{ApiKeyAuth, Auth, AuthConfig, JwtAuth};
+use oramacore_client::client::OramaClient;
+use oramacore_client::stream_manager::{AnswerConfig, StreamChunk};
+use oramacore_client::{OramaCoreStream, OramaError};
+
+async fn session(auth: AuthConfig) -> OramaCoreStream {
+    let auth = Auth::new(auth, Arc::new(reqwest::Client::new()));
+    let client = OramaClient::new(auth).unwrap();
+    OramaCoreStream::new("col".to_string(), client)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn invalid_reader_url_is_a_config_error_naming_the_url() {
+    let session = session(AuthConfig::ApiKey(
+        ApiKeyAuth::new("key").with_reader_url("not a url"),
+    ))
+    .await;
+
+    let error = match session.answer_stream(AnswerConfig::new("q")).await {
+        Ok(_) => panic!("stream opened against an invalid URL"),
+        Err(error) => error,
+    };
+
+    assert!(matches!(error, OramaError::Config { .. }));
+    let message = error.to_string();
+    assert!(message.contains("'not a url'"));
+    assert!(message.contains("Reader"));
+}
+
+#[tokio::test]
+async fn stream_refreshes_the_jwt_and_reconnects_after_a_401() {
+    let mut server = mockito::Server::new_async().await;
+    let reader_url = server.url();
+    let fetches = Arc::new(AtomicUsize::new(0));
+
+    let counter = fetches.clone();
+    let jwt = server
+        .mock("POST", "/api/user/jwt")
+        .with_body_from_request(move |_| {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            serde_json::json!({
+                "jwt": format!("jwt-{n}"),
+                "writerURL": reader_url,
+                "readerApiKey": format!("reader-{n}"),
+                "readerURL": reader_url,
+                "expiresIn": 3600,
+            })
+            .to_string()
+            .into()
+        })
+        .expect(2)
+        .create_async()
+        .await;
+    let rejected = server
+        .mock("POST", "/v1/collections/col/ai/answer/stream")
+        .match_header("authorization", "Bearer reader-1")
+        .with_status(401)
+        .expect(1)
+        .create_async()
+        .await;
+    let accepted = server
+        .mock("POST", "/v1/collections/col/ai/answer/stream")
+        .match_header("authorization", "Bearer reader-2")
+        .with_header("content-type", "text/event-stream")
+        .with_body("data: {\"content\":\"hi\"}\n\ndata: [DONE]\n\n")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let session = session(AuthConfig::Jwt(JwtAuth::new(
+        format!("{}/api/user/jwt", server.url()),
+        "col".to_string(),
+        "p_key".to_string(),
+    )))
+    .await;
+
+    let chunks: Vec<_> = session
+        .answer_stream(AnswerConfig::new("q"))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    jwt.assert_async().await;
+    rejected.assert_async().await;
+    accepted.assert_async().await;
+    assert!(chunks
+        .iter()
+        .any(|chunk| matches!(chunk, Ok(StreamChunk::Content(c)) if c == "hi")));
+    assert!(matches!(chunks.last(), Some(Ok(StreamChunk::Done))));
+}
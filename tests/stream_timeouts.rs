@@ -0,0 +1,232 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use oramacore_client::auth::{ApiKeyAuth, Auth, AuthConfig};
+use oramacore_client::client::OramaClient;
+use oramacore_client::stream_manager::{
+    AnswerConfig, CreateAiSessionConfig, StreamChunk, StreamConfig,
+};
+use oramacore_client::OramaCoreStream;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Serve one SSE response, writing each frame after its delay, then close
+/// the connection
+async fn serve_sse(frames: Vec<(Duration, &'static str)>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 8192];
+        let _ = socket.read(&mut buf).await;
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\n")
+            .await
+            .unwrap();
+
+        for (delay, data) in frames {
+            tokio::time::sleep(delay).await;
+            let frame = format!("data: {data}\n\n");
+            if socket.write_all(frame.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    url
+}
+
+async fn session(url: String, stream_config: StreamConfig) -> OramaCoreStream {
+    let auth = Auth::new(
+        AuthConfig::ApiKey(ApiKeyAuth::new("key").with_reader_url(url)),
+        Arc::new(reqwest::Client::new()),
+    );
+    let client = OramaClient::new(auth).unwrap();
+    OramaCoreStream::with_stream_config(
+        "col".to_string(),
+        client,
+        CreateAiSessionConfig::new(),
+        stream_config,
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn idle_only_stream_outlives_any_total_cap() {
+    let gap = Duration::from_millis(600);
+    let url = serve_sse(vec![
+        (gap, r#"{"content":"a"}"#),
+        (gap, r#"{"content":"b"}"#),
+        (gap, r#"{"content":"c"}"#),
+        (gap, "[DONE]"),
+    ])
+    .await;
+    let stream_config = StreamConfig {
+        stream_timeout: None,
+        idle_timeout: Some(1),
+        ..StreamConfig::default()
+    };
+    let session = session(url, stream_config).await;
+
+    let chunks: Vec<_> = session
+        .answer_stream(AnswerConfig::new("q"))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    let content: String = chunks
+        .iter()
+        .filter_map(|chunk| match chunk {
+            Ok(StreamChunk::Content(content)) => Some(content.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(content, "abc");
+    assert!(chunks.iter().all(Result::is_ok));
+    assert!(matches!(chunks.last(), Some(Ok(StreamChunk::Done))));
+}
+
+#[tokio::test]
+async fn idle_timeout_fires_on_a_silent_connection_and_ends_the_stream() {
+    let url = serve_sse(vec![
+        (Duration::ZERO, r#"{"content":"a"}"#),
+        (Duration::from_secs(30), "[DONE]"),
+    ])
+    .await;
+    let stream_config = StreamConfig {
+        stream_timeout: None,
+        idle_timeout: Some(1),
+        ..StreamConfig::default()
+    };
+    let session = session(url, stream_config).await;
+
+    let chunks: Vec<_> = tokio::time::timeout(
+        Duration::from_secs(5),
+        session
+            .answer_stream(AnswerConfig::new("q"))
+            .await
+            .unwrap()
+            .collect::<Vec<_>>(),
+    )
+    .await
+    .expect("idle timeout did not fire");
+
+    let error = chunks.last().unwrap().as_ref().unwrap_err();
+    assert!(error.to_string().contains("idle timeout"));
+    assert_eq!(chunks.iter().filter(|chunk| chunk.is_err()).count(), 1);
+
+    let interaction = session.last_interaction().await.unwrap();
+    assert!(interaction.error);
+    assert!(!interaction.loading);
+}
+
+#[tokio::test]
+async fn stream_timeout_caps_a_stream_that_keeps_sending() {
+    let gap = Duration::from_millis(300);
+    let url = serve_sse(vec![(gap, r#"{"content":"a"}"#); 10]).await;
+    let stream_config = StreamConfig {
+        stream_timeout: Some(1),
+        idle_timeout: Some(1),
+        ..StreamConfig::default()
+    };
+    let session = session(url, stream_config).await;
+
+    let chunks: Vec<_> = session
+        .answer_stream(AnswerConfig::new("q"))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    let error = chunks.last().unwrap().as_ref().unwrap_err();
+    assert!(error.to_string().contains("Stream timeout after 1 seconds"));
+    assert!(chunks.len() < 10);
+}
+
+#[tokio::test]
+async fn heartbeats_reset_the_idle_timeout() {
+    let gap = Duration::from_millis(600);
+    let url = serve_sse(vec![
+        (gap, "heartbeat"),
+        (gap, "heartbeat"),
+        (gap, "heartbeat"),
+        (gap, r#"{"content":"done thinking"}"#),
+        (Duration::ZERO, "[DONE]"),
+    ])
+    .await;
+    let stream_config = StreamConfig {
+        idle_timeout: Some(1),
+        ..StreamConfig::default()
+    };
+    let session = session(url, stream_config).await;
+
+    let chunks: Vec<_> = session
+        .answer_stream(AnswerConfig::new("q"))
+        .await
+        .unwrap()
+        .take_while(|chunk| futures::future::ready(!matches!(chunk, Ok(StreamChunk::Done))))
+        .collect()
+        .await;
+
+    assert!(chunks.iter().all(Result::is_ok));
+    assert!(chunks
+        .iter()
+        .any(|chunk| matches!(chunk, Ok(StreamChunk::Content(text)) if text == "done thinking")));
+}
+
+#[tokio::test]
+async fn connection_timeout_does_not_cut_off_a_longer_stream() {
+    let gap = Duration::from_millis(400);
+    let url = serve_sse(vec![
+        (gap, r#"{"content":"a"}"#),
+        (gap, r#"{"content":"b"}"#),
+        (gap, r#"{"content":"c"}"#),
+        (gap, r#"{"content":"d"}"#),
+        (Duration::ZERO, "[DONE]"),
+    ])
+    .await;
+    let stream_config = StreamConfig {
+        connection_timeout: 1,
+        ..StreamConfig::default()
+    };
+    let session = session(url, stream_config).await;
+
+    let chunks: Vec<_> = session
+        .answer_stream(AnswerConfig::new("q"))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert!(chunks.iter().all(Result::is_ok));
+    assert!(matches!(chunks.last(), Some(Ok(StreamChunk::Done))));
+}
+
+#[tokio::test]
+async fn connection_timeout_bounds_opening_the_stream() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    // Accept the connection but never answer
+    tokio::spawn(async move {
+        let (_socket, _) = listener.accept().await.unwrap();
+        tokio::time::sleep(Duration::from_secs(30)).await;
+    });
+    let stream_config = StreamConfig {
+        connection_timeout: 1,
+        ..StreamConfig::default()
+    };
+    let session = session(url, stream_config).await;
+
+    let error = match session.answer_stream(AnswerConfig::new("q")).await {
+        Ok(_) => panic!("stream opened against a silent server"),
+        Err(error) => error,
+    };
+
+    assert!(error
+        .to_string()
+        .contains("Stream connection timeout after 1 seconds"));
+}
@@ -0,0 +1,46 @@
+use oramacore_client::SystemPromptUsageMode;
+
+mod common;
+
+use common::manager;
+
+#[tokio::test]
+async fn prompts_are_filtered_by_usage_mode() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/v1/collections/col/system_prompts/all")
+        .match_query(mockito::Matcher::Any)
+        .with_body(
+            serde_json::json!({
+                "system_prompts": [
+                    { "id": "a", "name": "a", "prompt": "p", "usage_mode": "automatic" },
+                    { "id": "m", "name": "m", "prompt": "p", "usage_mode": "manual" },
+                    { "id": "n", "name": "n", "prompt": "p" }
+                ]
+            })
+            .to_string(),
+        )
+        .expect(3)
+        .create_async()
+        .await;
+    let manager = manager(&server).await;
+
+    let ids = |prompts: Vec<oramacore_client::SystemPrompt>| {
+        prompts.into_iter().map(|p| p.id).collect::<Vec<_>>()
+    };
+    let automatic = manager
+        .system_prompts
+        .get_all_by_mode(SystemPromptUsageMode::Automatic)
+        .await
+        .unwrap();
+    let manual = manager
+        .system_prompts
+        .get_all_by_mode(SystemPromptUsageMode::Manual)
+        .await
+        .unwrap();
+
+    assert_eq!(ids(automatic), ["a"]);
+    // A prompt without a usage mode counts as manual
+    assert_eq!(ids(manual), ["m", "n"]);
+    assert_eq!(manager.system_prompts.count().await.unwrap(), 3);
+}
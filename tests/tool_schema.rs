@@ -0,0 +1,35 @@
+#![cfg(feature = "schemars")]
+
+use oramacore_client::{FunctionCall, Tool};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize, JsonSchema)]
+struct WeatherArgs {
+    city: String,
+    days: Option<u32>,
+}
+
+#[test]
+fn tool_schema_round_trips_through_the_argument_type() {
+    let tool = Tool::from_type::<WeatherArgs>("get_weather", "Get the forecast").unwrap();
+
+    assert_eq!(tool.id, "get_weather");
+    assert_eq!(tool.description, "Get the forecast");
+    assert_eq!(tool.parameters["type"], "object");
+    assert!(tool.parameters["properties"]["city"].is_object());
+    assert!(tool.parameters["properties"]["days"].is_object());
+    assert_eq!(tool.parameters["required"], serde_json::json!(["city"]));
+
+    let call = FunctionCall {
+        name: "get_weather".to_string(),
+        arguments: r#"{"city":"Rome","days":3}"#.to_string(),
+    };
+    assert_eq!(
+        call.parsed_arguments::<WeatherArgs>().unwrap(),
+        WeatherArgs {
+            city: "Rome".to_string(),
+            days: Some(3),
+        }
+    );
+}
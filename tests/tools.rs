@@ -0,0 +1,79 @@
+use oramacore_client::InsertToolBody;
+
+mod common;
+
+use common::manager;
+
+fn tool() -> InsertToolBody {
+    InsertToolBody {
+        id: "get_weather".to_string(),
+        description: "Get the forecast".to_string(),
+        parameters: serde_json::json!({ "type": "object" }),
+        code: None,
+        system_prompt: None,
+    }
+}
+
+#[tokio::test]
+async fn validate_returns_a_typed_response() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/tools/validate")
+        .match_header("authorization", "Bearer key")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "id": "get_weather" }),
+        ))
+        .with_body(
+            serde_json::json!({
+                "result": {
+                    "security": { "valid": true, "reason": "ok", "violations": [] },
+                    "schema": {
+                        "valid": false,
+                        "reason": "missing properties",
+                        "parameter_issues": [
+                            { "parameter": "city", "issue": "no type" }
+                        ]
+                    },
+                    "overall_assessment": { "valid": false, "summary": "fix the schema" }
+                }
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let response = manager(&server).await.tools.validate(tool()).await.unwrap();
+
+    mock.assert_async().await;
+    assert!(response.security.valid);
+    assert!(!response.schema.valid);
+    assert_eq!(response.schema.parameter_issues.len(), 1);
+    assert_eq!(response.schema.parameter_issues[0].parameter, "city");
+    assert_eq!(response.schema.parameter_issues[0].issue, "no type");
+    assert!(!response.overall_assessment.valid);
+    assert_eq!(response.overall_assessment.summary, "fix the schema");
+}
+
+#[tokio::test]
+async fn parameter_issues_default_to_empty() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/v1/collections/col/tools/validate")
+        .with_body(
+            serde_json::json!({
+                "result": {
+                    "security": { "valid": true, "reason": "ok", "violations": [] },
+                    "schema": { "valid": true, "reason": "ok" },
+                    "overall_assessment": { "valid": true, "summary": "ok" }
+                }
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let response = manager(&server).await.tools.validate(tool()).await.unwrap();
+
+    assert!(response.schema.valid);
+    assert!(response.schema.parameter_issues.is_empty());
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use oramacore_client::collection::{CollectionManagerConfig, Endpoints};
+use oramacore_client::{CollectionManager, SearchParams};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+/// Span name with its recorded fields
+type RecordedSpan = (String, Vec<(String, String)>);
+
+/// Layer that records every span opened
+#[derive(Clone, Default)]
+struct CaptureLayer {
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+}
+
+struct FieldVisitor(Vec<(String, String)>);
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{value:?}")));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+}
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor(Vec::new());
+        attrs.record(&mut visitor);
+        self.spans
+            .lock()
+            .unwrap()
+            .push((attrs.metadata().name().to_string(), visitor.0));
+    }
+}
+
+#[tokio::test]
+async fn search_emits_a_span_with_the_collection_id() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/collections/col/search")
+        .match_query(mockito::Matcher::UrlEncoded("api-key".into(), "key".into()))
+        .with_body(r#"{"count":0,"hits":[]}"#)
+        .create_async()
+        .await;
+
+    let layer = CaptureLayer::default();
+    let subscriber = tracing_subscriber::registry().with(layer.clone());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()));
+    let manager = CollectionManager::new(config).await.unwrap();
+    manager
+        .search::<serde_json::Value>(&SearchParams::new("shoes"))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    let spans = layer.spans.lock().unwrap();
+    let (_, fields) = spans
+        .iter()
+        .find(|(name, _)| name == "search")
+        .expect("no search span recorded");
+    assert!(fields
+        .iter()
+        .any(|(name, value)| name == "collection_id" && value == "col"));
+}
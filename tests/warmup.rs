@@ -0,0 +1,43 @@
+use oramacore_client::collection::{CollectionManagerConfig, Endpoints};
+use oramacore_client::CollectionManager;
+
+async fn manager(reader_url: String, writer_url: String) -> CollectionManager {
+    let endpoints = Endpoints::new()
+        .with_reader_url(reader_url)
+        .with_writer_url(writer_url);
+    let config = CollectionManagerConfig::new("col", "key").with_endpoints(endpoints);
+    CollectionManager::new(config).await.unwrap()
+}
+
+#[tokio::test]
+async fn warmup_connects_to_reader_and_writer() {
+    let mut reader = mockito::Server::new_async().await;
+    let mut writer = mockito::Server::new_async().await;
+    let reader_head = reader.mock("HEAD", "/").expect(1).create_async().await;
+    let writer_head = writer.mock("HEAD", "/").expect(1).create_async().await;
+
+    manager(reader.url(), writer.url())
+        .await
+        .warmup()
+        .await
+        .unwrap();
+
+    reader_head.assert_async().await;
+    writer_head.assert_async().await;
+}
+
+#[tokio::test]
+async fn warmup_ignores_unreachable_hosts() {
+    let mut reader = mockito::Server::new_async().await;
+    let reader_head = reader.mock("HEAD", "/").expect(1).create_async().await;
+    // Nothing listens on port 1
+    let writer_url = "http://127.0.0.1:1".to_string();
+
+    manager(reader.url(), writer_url)
+        .await
+        .warmup()
+        .await
+        .unwrap();
+
+    reader_head.assert_async().await;
+}
@@ -0,0 +1,42 @@
+use oramacore_client::collection::{CollectionManagerConfig, Endpoints};
+use oramacore_client::{CollectionManager, OramaError};
+
+#[tokio::test]
+async fn stale_upsert_is_a_conflict_with_version_and_request_context() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock(
+            "POST",
+            "/v1/collections/col/indexes/products/documents/upsert",
+        )
+        .with_status(409)
+        .with_body(r#"{"message":"version mismatch","current_version":3}"#)
+        .create_async()
+        .await;
+
+    let config = CollectionManagerConfig::new("col", "key")
+        .with_endpoints(Endpoints::from_base_url(server.url()));
+    let manager = CollectionManager::new(config).await.unwrap();
+
+    let error = manager
+        .index
+        .set("products".to_string())
+        .upsert_documents_if_version(vec![serde_json::json!({ "id": "1" })], 2)
+        .await
+        .unwrap_err();
+
+    mock.assert_async().await;
+    assert!(matches!(
+        error,
+        OramaError::Conflict {
+            current_version: Some(3),
+            ..
+        }
+    ));
+    let context = error.context().expect("conflict without request context");
+    assert_eq!(context.method, "POST");
+    assert_eq!(
+        context.path,
+        "/v1/collections/col/indexes/products/documents/upsert"
+    );
+}